@@ -0,0 +1,85 @@
+//! Representative documents for the parser benchmarks, generated so they
+//! don't have to be checked in.
+
+use json_parser::JsonValue;
+
+/// The benchmark inputs, by name.
+pub fn all() -> Vec<(&'static str, String)> {
+    let records = records(2_000);
+    vec![
+        ("records (pretty)", records.to_string_pretty(2)),
+        ("records (minified)", records.to_string()),
+        ("numbers", numbers(50_000)),
+        ("strings", strings(20_000)),
+        ("large array", large_array(100_000)),
+        ("deep", deep(60)),
+        ("small containers", small_containers(20_000)),
+    ]
+}
+
+// An array of flat-ish objects, like a typical API response.
+fn records(count: usize) -> JsonValue {
+    let items = (0..count)
+        .map(|i| {
+            json_parser::json!({
+                "id": i as i64,
+                "name": format!("user {}", i),
+                "email": format!("user{}@example.com", i),
+                "active": i % 3 != 0,
+                "score": i as f64 * 1.25,
+                "tags": ["alpha", "beta", format!("tag{}", i % 10)],
+                "address": {"city": "北京", "zip": format!("{:05}", i), "geo": [39.9, 116.4]},
+                "manager": null
+            })
+        })
+        .collect();
+    JsonValue::Array(items)
+}
+
+fn numbers(count: usize) -> String {
+    let numbers: Vec<String> = (0..count)
+        .map(|i| match i % 4 {
+            0 => i.to_string(),
+            1 => format!("-{}.{}", i, i % 97),
+            2 => format!("{}e-{}", i, i % 12),
+            _ => format!("{}", i as f64 / 7.0),
+        })
+        .collect();
+    format!("[{}]", numbers.join(", "))
+}
+
+// One long array of small integers, for the cost of growing a big array.
+fn large_array(count: usize) -> String {
+    let numbers: Vec<String> = (0..count).map(|i| (i % 1000).to_string()).collect();
+    format!("[{}]", numbers.join(","))
+}
+
+fn strings(count: usize) -> String {
+    let strings: Vec<String> = (0..count)
+        .map(|i| match i % 3 {
+            0 => format!("\"plain string number {}\"", i),
+            1 => format!("\"escaped \\\"quotes\\\" and \\\\ slashes \\n {}\"", i),
+            _ => format!("\"unicode \\u00e9\\u4e2d \\uD83D\\uDE00 {}\"", i),
+        })
+        .collect();
+    format!("[\n  {}\n]", strings.join(",\n  "))
+}
+
+fn deep(depth: usize) -> String {
+    let one = format!("{}1{}", "[{\"k\": ".repeat(depth), "}]".repeat(depth));
+    format!("[{}]", vec![one; 200].join(",\n"))
+}
+
+// Many short, sometimes empty, containers with whitespace around their
+// brackets, where skipping whitespace near the brackets dominates.
+fn small_containers(count: usize) -> String {
+    let items: Vec<&str> = (0..count)
+        .map(|i| match i % 4 {
+            0 => "{ }",
+            1 => "[\n    ]",
+            2 => "{ \"a\" : [ 1 , 2 ] , \"b\" : { } }",
+            _ => "[ [ ] , { \"c\" : null } ]",
+        })
+        .collect();
+    format!("[\n  {}\n]", items.join(" ,\n  "))
+}
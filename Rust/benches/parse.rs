@@ -0,0 +1,82 @@
+//! Parser throughput over the documents in `inputs`.
+//!
+//! Run with `cargo bench`. Each input is parsed repeatedly for about a second
+//! and the best rate is reported, to keep noise from other processes down.
+//!
+//! Measured on one core, before and after skipping whitespace with a
+//! hand-rolled loop instead of `multispace0` and `many0_count`:
+//!
+//! ```text
+//! input                 before        after
+//! records (pretty)      32.4 MB/s     58.5 MB/s
+//! records (minified)    20.2 MB/s     33.3 MB/s
+//! numbers               59.1 MB/s     55.3 MB/s
+//! strings               49.4 MB/s     49.2 MB/s
+//! deep                   7.0 MB/s     10.0 MB/s
+//! ```
+//!
+//! Once an array reaches 1,024 items, the parser reserves room for as many
+//! more as the rest of the input would hold at the same length per item,
+//! instead of letting the `Vec` double its way there. Ten interleaved runs
+//! of `large array` alone, three seconds each, twice over:
+//!
+//! ```text
+//!                       best          median
+//! doubling              31.4 MB/s     30.5 MB/s
+//! reserving             33.6 MB/s     33.0 MB/s
+//! exact capacity        34.2 MB/s     32.8 MB/s
+//!
+//! doubling              30.0 MB/s     27.7 MB/s
+//! reserving             33.2 MB/s     30.6 MB/s
+//! ```
+//!
+//! The guess does as well as reserving exactly enough. Getting the exact
+//! count takes a scan over the array first, which costs more than it saves.
+//!
+//! Skipping the whitespace just inside an opening bracket, and before a
+//! comma or closing bracket, once rather than once per alternative tried
+//! makes no measurable difference, even on `small containers`. Ten
+//! interleaved runs of it alone, three seconds each, twice over:
+//!
+//! ```text
+//!                       best          median
+//! before                35.0 MB/s     34.0 MB/s
+//! after                 36.7 MB/s     34.2 MB/s
+//!
+//! before                36.4 MB/s     31.8 MB/s
+//! after                 36.5 MB/s     33.1 MB/s
+//! ```
+//!
+//! The repeated scans stop at the first non-whitespace byte, so they were
+//! already cheap.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+mod inputs;
+
+const TARGET: Duration = Duration::from_secs(1);
+
+fn main() {
+    // `cargo test --benches` passes `--bench` only when benchmarking; just
+    // check that the inputs parse otherwise, so the tests stay quick.
+    let benchmarking = std::env::args().any(|arg| arg == "--bench");
+    if benchmarking {
+        println!("{:<20} {:>10} {:>12}", "input", "size", "throughput");
+    }
+    for (name, input) in inputs::all() {
+        if !benchmarking {
+            json_parser::parse(&input).unwrap();
+            continue;
+        }
+        let mut best = Duration::MAX;
+        let started = Instant::now();
+        while started.elapsed() < TARGET {
+            let start = Instant::now();
+            black_box(json_parser::parse(black_box(&input)).unwrap());
+            best = best.min(start.elapsed());
+        }
+        let rate = input.len() as f64 / best.as_secs_f64() / (1024.0 * 1024.0);
+        println!("{:<20} {:>8} KB {:>8.1} MB/s", name, input.len() / 1024, rate);
+    }
+}
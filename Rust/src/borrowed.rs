@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+
+use crate::parser::{Builder, Number, RawStr};
+use crate::{JsonValue, Map};
+
+/// A parsed JSON document that borrows its strings from the input.
+///
+/// Returned by [`parse_borrowed`](crate::parse_borrowed). A string without
+/// escapes is a slice of the input, so only strings that contain escapes
+/// need an allocation of their own. Keys are treated the same way.
+///
+/// Object members are kept exactly as they appear in the document, duplicates
+/// included, and equality compares them in order. [`get`](JsonValueRef::get)
+/// returns the last member with a given key, which is the one
+/// [`parse`](crate::parse) would have kept.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueRef<'a> {
+    Null,
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(Cow<'a, str>),
+    Array(Vec<JsonValueRef<'a>>),
+    Object(Vec<(Cow<'a, str>, JsonValueRef<'a>)>),
+}
+
+impl<'a> JsonValueRef<'a> {
+    /// Returns the string if the value is a `Str`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValueRef::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Looks up a member of an object, returning `None` if the value isn't an
+    /// object or has no such member.
+    pub fn get(&self, key: &str) -> Option<&JsonValueRef<'a>> {
+        match self {
+            JsonValueRef::Object(members) => members.iter().rev().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Copies the value into an owned [`JsonValue`].
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Int(i) => JsonValue::Int(i),
+            JsonValueRef::Float(f) => JsonValue::Float(f),
+            JsonValueRef::Bool(b) => JsonValue::Bool(b),
+            JsonValueRef::Str(s) => JsonValue::Str(s.into_owned()),
+            JsonValueRef::Array(items) => JsonValue::Array(items.into_iter().map(JsonValueRef::into_owned).collect()),
+            JsonValueRef::Object(members) => {
+                let members: Map =
+                    members.into_iter().map(|(key, value)| (key.into_owned(), value.into_owned())).collect();
+                JsonValue::Object(members)
+            }
+        }
+    }
+}
+
+/// Builds a [`JsonValueRef`] tree.
+pub(crate) struct RefBuilder;
+
+impl<'a> Builder<'a> for RefBuilder {
+    type Value = JsonValueRef<'a>;
+    type Array = Vec<JsonValueRef<'a>>;
+    type Object = Vec<(Cow<'a, str>, JsonValueRef<'a>)>;
+
+    fn null(&self) -> JsonValueRef<'a> {
+        JsonValueRef::Null
+    }
+
+    fn bool(&self, b: bool) -> JsonValueRef<'a> {
+        JsonValueRef::Bool(b)
+    }
+
+    fn number(&self, lexeme: &'a str) -> JsonValueRef<'a> {
+        match Number::from_lexeme(lexeme) {
+            Number::Int(i) => JsonValueRef::Int(i),
+            Number::Float(f) => JsonValueRef::Float(f),
+        }
+    }
+
+    fn non_finite(&self, n: f64) -> JsonValueRef<'a> {
+        JsonValueRef::Float(n)
+    }
+
+    fn string(&self, s: RawStr<'a>) -> JsonValueRef<'a> {
+        JsonValueRef::Str(s.decode())
+    }
+
+    fn array(&self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&self, array: &mut Self::Array, value: JsonValueRef<'a>) {
+        array.push(value);
+    }
+
+    fn finish_array(&self, array: Self::Array) -> JsonValueRef<'a> {
+        JsonValueRef::Array(array)
+    }
+
+    fn object(&self) -> Self::Object {
+        Vec::new()
+    }
+
+    fn member(&self, object: &mut Self::Object, key: RawStr<'a>, value: JsonValueRef<'a>) {
+        object.push((key.decode(), value));
+    }
+
+    fn finish_object(&self, object: Self::Object) -> JsonValueRef<'a> {
+        JsonValueRef::Object(object)
+    }
+}
@@ -0,0 +1,81 @@
+//! Fluent construction of objects and arrays.
+
+use crate::{JsonValue, Map};
+
+/// Builds a [`JsonValue::Object`] one member at a time, starting from
+/// [`JsonValue::object`].
+///
+/// ```
+/// use json_parser::{json, JsonValue};
+///
+/// let value = JsonValue::object()
+///     .insert("name", "张三")
+///     .insert("scores", JsonValue::array().push(90).push(85))
+///     .build();
+/// assert_eq!(value, json!({"name": "张三", "scores": [90, 85]}));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ObjectBuilder {
+    members: Map,
+}
+
+impl ObjectBuilder {
+    /// Adds a member. As with [`Map::insert`], a repeated key replaces the
+    /// earlier value but keeps its position.
+    pub fn insert(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.members.insert(key.into(), value.into());
+        self
+    }
+
+    /// Finishes the object, with its members in insertion order.
+    pub fn build(self) -> JsonValue {
+        JsonValue::Object(self.members)
+    }
+}
+
+/// Builds a [`JsonValue::Array`] one element at a time, starting from
+/// [`JsonValue::array`].
+#[derive(Debug, Clone, Default)]
+pub struct ArrayBuilder {
+    items: Vec<JsonValue>,
+}
+
+impl ArrayBuilder {
+    /// Adds an element at the end.
+    pub fn push(mut self, value: impl Into<JsonValue>) -> Self {
+        self.items.push(value.into());
+        self
+    }
+
+    /// Finishes the array.
+    pub fn build(self) -> JsonValue {
+        JsonValue::Array(self.items)
+    }
+}
+
+/// Lets a nested builder be passed as a value without calling `build`.
+impl From<ObjectBuilder> for JsonValue {
+    fn from(builder: ObjectBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl From<ArrayBuilder> for JsonValue {
+    fn from(builder: ArrayBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl JsonValue {
+    /// Starts building an object with chained
+    /// [`insert`](ObjectBuilder::insert) calls.
+    pub fn object() -> ObjectBuilder {
+        ObjectBuilder::default()
+    }
+
+    /// Starts building an array with chained [`push`](ArrayBuilder::push)
+    /// calls.
+    pub fn array() -> ArrayBuilder {
+        ArrayBuilder::default()
+    }
+}
@@ -0,0 +1,91 @@
+//! Pivoting arrays of records into columns.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{JsonValue, Map, TypeError};
+
+/// The error returned by [`JsonValue::from_columns`] when the columns aren't
+/// all the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLengthError {
+    /// The first column, in key order, whose length differs from the first
+    /// column's.
+    pub key: String,
+    /// How many values it has.
+    pub len: usize,
+    /// How many values the first column has.
+    pub expected: usize,
+}
+
+impl fmt::Display for ColumnLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {:?} has length {}, expected {}", self.key, self.len, self.expected)
+    }
+}
+
+impl std::error::Error for ColumnLengthError {}
+
+impl JsonValue {
+    /// Pivots an array of objects into columns: each key that appears in any
+    /// of them maps to that member's value in every object, in array order,
+    /// with `null` where an object doesn't have it. So every column is as
+    /// long as the array.
+    ///
+    /// A value that isn't an array, or has an element that isn't an object,
+    /// is a [`TypeError`].
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let columns = json!([{"a": 1, "b": 2}, {"a": 3}]).to_columns().unwrap();
+    /// assert_eq!(columns["a"], [json!(1), json!(3)]);
+    /// assert_eq!(columns["b"], [json!(2), json!(null)]);
+    /// ```
+    pub fn to_columns(&self) -> Result<HashMap<String, Vec<JsonValue>>, TypeError> {
+        let JsonValue::Array(rows) = self else {
+            return Err(TypeError { expected: "an array", found: self.kind() });
+        };
+        let mut columns: HashMap<String, Vec<JsonValue>> = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            let JsonValue::Object(members) = row else {
+                return Err(TypeError { expected: "an object", found: row.kind() });
+            };
+            for (key, value) in members.iter() {
+                let column = columns.entry(key.to_string()).or_insert_with(|| vec![JsonValue::Null; i]);
+                column.push(value.clone());
+            }
+            // Pad the columns this row didn't have.
+            for column in columns.values_mut() {
+                if column.len() == i {
+                    column.push(JsonValue::Null);
+                }
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Pivots columns back into an array of objects, the inverse of
+    /// [`to_columns`](JsonValue::to_columns): the `i`th object has a member
+    /// for every column, holding its `i`th value. The members are sorted by
+    /// key, since the input map has no order, and `null`s are kept as they
+    /// are.
+    ///
+    /// Every column must have the same length, or this fails with a
+    /// [`ColumnLengthError`].
+    pub fn from_columns(columns: HashMap<String, Vec<JsonValue>>) -> Result<JsonValue, ColumnLengthError> {
+        let mut columns: Vec<_> = columns.into_iter().collect();
+        columns.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let expected = columns.first().map_or(0, |(_, values)| values.len());
+        if let Some((key, values)) = columns.iter().find(|(_, values)| values.len() != expected) {
+            return Err(ColumnLengthError { key: key.clone(), len: values.len(), expected });
+        }
+        let mut rows = vec![Map::new(); expected];
+        for (key, values) in columns {
+            for (row, value) in rows.iter_mut().zip(values) {
+                row.insert(key.clone(), value);
+            }
+        }
+        Ok(JsonValue::Array(rows.into_iter().map(JsonValue::Object).collect()))
+    }
+}
@@ -0,0 +1,274 @@
+//! Conversions from [`JsonValue`] into Rust types.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::value::integer_digits;
+use crate::{JsonValue, Map};
+
+/// The error returned when a [`JsonValue`] can't be converted with
+/// [`FromJson`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The value isn't the kind of value the target type converts from, such
+    /// as a string where a number was expected.
+    WrongType { expected: &'static str, found: &'static str },
+    /// The value is a whole number, but it doesn't fit in `target`.
+    OutOfRange { target: &'static str },
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::WrongType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+            ConvertError::OutOfRange { target } => write!(f, "number out of range for {}", target),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// The error returned by [`JsonValue::get_as`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LookupError {
+    /// Nothing is at `path`, or `path` isn't a valid JSON Pointer.
+    NotFound { path: String },
+    /// The value at `path` couldn't be converted.
+    Convert { path: String, error: ConvertError },
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::NotFound { path } => write!(f, "nothing found at {:?}", path),
+            LookupError::Convert { path, error } => write!(f, "{} at {:?}", error, path),
+        }
+    }
+}
+
+impl std::error::Error for LookupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LookupError::NotFound { .. } => None,
+            LookupError::Convert { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Types that can be built from a [`JsonValue`].
+///
+/// Implementations exist for `bool`, the integer and floating-point types,
+/// `String`, and for `Vec<T>`, `Option<T>` and `HashMap<String, T>` of any
+/// `T: FromJson`, so a struct can be converted by converting its members:
+///
+/// ```
+/// use json_parser::{json, ConvertError, FromJson, JsonValue};
+///
+/// struct User {
+///     name: String,
+///     scores: Vec<u32>,
+/// }
+///
+/// impl FromJson for User {
+///     fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+///         let field = |key| v.get(key).unwrap_or(&JsonValue::Null);
+///         Ok(User { name: String::from_json(field("name"))?, scores: Vec::from_json(field("scores"))? })
+///     }
+/// }
+///
+/// let user = User::from_json(&json!({"name": "Ann", "scores": [90, 85]})).unwrap();
+/// assert_eq!((user.name.as_str(), user.scores), ("Ann", vec![90, 85]));
+/// ```
+pub trait FromJson: Sized {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError>;
+}
+
+impl JsonValue {
+    // How the kind of value is described in a `ConvertError` or a
+    // `TypeError`.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::Int(_) | JsonValue::Float(_) | JsonValue::RawNum(_) | JsonValue::BigInt(_) => "a number",
+            JsonValue::Str(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+
+    fn wrong_type(&self, expected: &'static str) -> ConvertError {
+        ConvertError::WrongType { expected, found: self.kind() }
+    }
+
+    /// Looks up the value at the JSON Pointer `ptr`, as
+    /// [`pointer`](JsonValue::pointer) does, and converts it into `T`.
+    ///
+    /// A missing value is an error even when `T` is an `Option`, which only
+    /// turns an explicit `null` into `None`.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let doc = json!({"user": {"age": 30, "tags": ["a", "b"]}});
+    /// assert_eq!(doc.get_as::<f64>("/user/age"), Ok(30.0));
+    /// assert_eq!(doc.get_as::<Vec<String>>("/user/tags").unwrap(), ["a", "b"]);
+    /// ```
+    pub fn get_as<T: FromJson>(&self, ptr: &str) -> Result<T, LookupError> {
+        let value = self.pointer(ptr).ok_or_else(|| LookupError::NotFound { path: ptr.to_string() })?;
+        T::from_json(value).map_err(|error| LookupError::Convert { path: ptr.to_string(), error })
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        v.as_bool().ok_or_else(|| v.wrong_type("a boolean"))
+    }
+}
+
+// The value as a `u64`, for the whole numbers past `i64::MAX` that
+// `as_i64` gives up on. Integer lexemes are read exactly, not through `f64`.
+fn as_u64(v: &JsonValue) -> Option<u64> {
+    match *v {
+        JsonValue::RawNum(ref s) | JsonValue::BigInt(ref s) => match integer_digits(s) {
+            Some((false, digits)) => digits.parse().ok(),
+            Some((true, _)) => None,
+            None => as_u64(&JsonValue::Float(s.parse().ok()?)),
+        },
+        // 2^64 is the first value past u64::MAX.
+        JsonValue::Float(f) if f.fract() == 0.0 && (0.0..1.844_674_407_370_955_2e19).contains(&f) => Some(f as u64),
+        _ => None,
+    }
+}
+
+// Integers accept any number with no fractional part, as `as_i64` does, and
+// unsigned ones also those up to `u64::MAX`.
+macro_rules! impl_from_json_int {
+    ($($t:ty),*) => {$(
+        impl FromJson for $t {
+            fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+                let out_of_range = ConvertError::OutOfRange { target: stringify!($t) };
+                match (v.as_i64(), v.as_f64()) {
+                    (Some(i), _) => <$t>::try_from(i).map_err(|_| out_of_range),
+                    (None, Some(n)) if n.fract() == 0.0 => {
+                        as_u64(v).and_then(|n| <$t>::try_from(n).ok()).ok_or(out_of_range)
+                    }
+                    _ => Err(v.wrong_type("an integer")),
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_json_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromJson for f64 {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        v.as_f64().ok_or_else(|| v.wrong_type("a number"))
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        f64::from_json(v).map(|n| n as f32)
+    }
+}
+
+impl FromJson for String {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        v.as_str().map(str::to_string).ok_or_else(|| v.wrong_type("a string"))
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        match v {
+            JsonValue::Array(items) => items.iter().map(T::from_json).collect(),
+            _ => Err(v.wrong_type("an array")),
+        }
+    }
+}
+
+/// `null` converts to `None`, and anything else to `Some` of its conversion.
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        match v {
+            JsonValue::Null => Ok(None),
+            _ => T::from_json(v).map(Some),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(v: &JsonValue) -> Result<Self, ConvertError> {
+        match v {
+            JsonValue::Object(members) => {
+                members.iter().map(|(key, value)| Ok((key.to_string(), T::from_json(value)?))).collect()
+            }
+            _ => Err(v.wrong_type("an object")),
+        }
+    }
+}
+
+/// Types that can be turned into a [`JsonValue`], the counterpart of
+/// [`FromJson`].
+///
+/// Unlike `From`, this is implemented for collections of any `T: IntoJson`,
+/// so typed data converts in one call:
+///
+/// ```
+/// use json_parser::IntoJson;
+///
+/// let value = vec![Some(1), None].into_json();
+/// assert_eq!(value.to_string(), "[1,null]");
+/// ```
+pub trait IntoJson {
+    fn into_json(self) -> JsonValue;
+}
+
+// Everything `JsonValue` already converts from.
+macro_rules! impl_into_json_via_from {
+    ($($t:ty),*) => {$(
+        impl IntoJson for $t {
+            fn into_json(self) -> JsonValue {
+                JsonValue::from(self)
+            }
+        }
+    )*};
+}
+
+impl_into_json_via_from!((), bool, i8, i16, i32, i64, u8, u16, u32, f32, f64, &str, String, Map);
+
+impl IntoJson for JsonValue {
+    fn into_json(self) -> JsonValue {
+        self
+    }
+}
+
+impl<T: IntoJson> IntoJson for Vec<T> {
+    fn into_json(self) -> JsonValue {
+        JsonValue::Array(self.into_iter().map(T::into_json).collect())
+    }
+}
+
+/// `None` becomes `null`.
+impl<T: IntoJson> IntoJson for Option<T> {
+    fn into_json(self) -> JsonValue {
+        self.map_or(JsonValue::Null, T::into_json)
+    }
+}
+
+/// The members take on the map's (unspecified) iteration order, as with
+/// `JsonValue::from`.
+impl<T: IntoJson> IntoJson for HashMap<String, T> {
+    fn into_json(self) -> JsonValue {
+        JsonValue::Object(self.into_iter().map(|(key, value)| (key, value.into_json())).collect())
+    }
+}
+
+/// The members are in key order.
+impl<T: IntoJson> IntoJson for BTreeMap<String, T> {
+    fn into_json(self) -> JsonValue {
+        JsonValue::Object(self.into_iter().map(|(key, value)| (key, value.into_json())).collect())
+    }
+}
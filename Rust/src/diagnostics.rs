@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+
+use crate::parser::{Builder, RawStr, ValueBuilder};
+use crate::{pointer, JsonValue, Map};
+
+/// A warning about a document that parsed, as returned by
+/// [`parse_with_diagnostics`](crate::parse_with_diagnostics).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The object at `path`, a JSON Pointer, has more than one member named
+    /// `key`. One is reported for each repeat, so a key given three times
+    /// is reported twice.
+    DuplicateKey { path: String, key: String },
+}
+
+// Where the value being parsed sits within its container.
+enum Segment {
+    Index(usize),
+    Key(String),
+}
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], keeping track of the path
+/// to the current value so that duplicate keys can be reported with it.
+#[derive(Default)]
+pub(crate) struct DiagnosticsBuilder {
+    path: RefCell<Vec<Segment>>,
+    diagnostics: RefCell<Vec<Diagnostic>>,
+}
+
+impl DiagnosticsBuilder {
+    pub(crate) fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics.into_inner()
+    }
+
+    // The pointer to the innermost open container.
+    fn container_path(&self) -> String {
+        let path = self.path.borrow();
+        let mut pointer = String::new();
+        for segment in &path[..path.len() - 1] {
+            pointer.push('/');
+            match segment {
+                Segment::Index(i) => pointer.push_str(&i.to_string()),
+                Segment::Key(key) => pointer.push_str(&pointer::escape(key)),
+            }
+        }
+        pointer
+    }
+}
+
+impl<'a> Builder<'a> for &DiagnosticsBuilder {
+    type Value = JsonValue;
+    type Array = Vec<JsonValue>;
+    type Object = Map;
+
+    fn null(&self) -> JsonValue {
+        ValueBuilder.null()
+    }
+
+    fn bool(&self, b: bool) -> JsonValue {
+        ValueBuilder.bool(b)
+    }
+
+    fn number(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.number(lexeme)
+    }
+
+    fn raw_number(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.raw_number(lexeme)
+    }
+
+    fn big_integer(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.big_integer(lexeme)
+    }
+
+    fn custom_number(&self, lexeme: &'a str, handler: &dyn Fn(&str) -> JsonValue) -> JsonValue {
+        ValueBuilder.custom_number(lexeme, handler)
+    }
+
+    fn non_finite(&self, n: f64) -> JsonValue {
+        ValueBuilder.non_finite(n)
+    }
+
+    fn string(&self, s: RawStr<'a>) -> JsonValue {
+        ValueBuilder.string(s)
+    }
+
+    fn array(&self) -> Vec<JsonValue> {
+        self.path.borrow_mut().push(Segment::Index(0));
+        ValueBuilder.array()
+    }
+
+    fn push(&self, array: &mut Vec<JsonValue>, value: JsonValue) {
+        if let Some(Segment::Index(i)) = self.path.borrow_mut().last_mut() {
+            *i += 1;
+        }
+        ValueBuilder.push(array, value);
+    }
+
+    fn reserve(&self, array: &mut Vec<JsonValue>, additional: usize) {
+        ValueBuilder.reserve(array, additional);
+    }
+
+    fn finish_array(&self, array: Vec<JsonValue>) -> JsonValue {
+        self.path.borrow_mut().pop();
+        ValueBuilder.finish_array(array)
+    }
+
+    fn object(&self) -> Map {
+        self.path.borrow_mut().push(Segment::Key(String::new()));
+        ValueBuilder.object()
+    }
+
+    fn key(&self, key: RawStr<'a>) {
+        if let Some(segment) = self.path.borrow_mut().last_mut() {
+            *segment = Segment::Key(key.decode().into_owned());
+        }
+    }
+
+    fn member(&self, object: &mut Map, key: RawStr<'a>, value: JsonValue) {
+        let key = key.decode();
+        if object.contains_key(&key) {
+            let path = self.container_path();
+            self.diagnostics.borrow_mut().push(Diagnostic::DuplicateKey { path, key: key.to_string() });
+        }
+        object.insert(key.into_owned(), value);
+    }
+
+    fn finish_object(&self, object: Map) -> JsonValue {
+        self.path.borrow_mut().pop();
+        ValueBuilder.finish_object(object)
+    }
+}
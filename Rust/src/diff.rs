@@ -0,0 +1,173 @@
+use crate::{pointer, JsonValue, Map};
+
+/// One difference between two values, as listed by
+/// [`JsonValue::changes`]. Each path is a JSON Pointer into the value it
+/// names: the new one for an addition, the old one otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A member or element only the new value has.
+    Added { path: String, value: JsonValue },
+    /// A member or element only the old value has.
+    Removed { path: String, value: JsonValue },
+    /// A value that differs in some other way, such as a changed number or
+    /// a string that became an array.
+    Modified { path: String, old: JsonValue, new: JsonValue },
+}
+
+impl JsonValue {
+    /// Describes how `other` differs from this value.
+    ///
+    /// Equal values produce an empty object. When both sides are objects the
+    /// result has up to three members: `"added"` and `"removed"` hold the
+    /// members only `other` or only `self` has, and `"changed"` maps each key
+    /// whose value differs to the diff of that value. Arrays are compared
+    /// index-wise the same way, keyed by the index as a string. Any other
+    /// difference, including a change of type, is written as
+    /// `{"from": old, "to": new}`.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let old = json!({"port": 80, "debug": true});
+    /// let new = json!({"port": 8080, "name": "web"});
+    /// assert_eq!(
+    ///     old.diff(&new),
+    ///     json!({
+    ///         "added": {"name": "web"},
+    ///         "removed": {"debug": true},
+    ///         "changed": {"port": {"from": 80, "to": 8080}}
+    ///     })
+    /// );
+    /// ```
+    pub fn diff(&self, other: &JsonValue) -> JsonValue {
+        let mut diff = Diff::default();
+        match (self, other) {
+            _ if self == other => {}
+            (JsonValue::Object(old), JsonValue::Object(new)) => {
+                for (key, value) in old {
+                    match new.get(key) {
+                        Some(new_value) => diff.compare(key.to_string(), value, new_value),
+                        None => {
+                            diff.removed.insert(key.to_string(), value.clone());
+                        }
+                    }
+                }
+                for (key, value) in new {
+                    if !old.contains_key(key) {
+                        diff.added.insert(key.to_string(), value.clone());
+                    }
+                }
+            }
+            (JsonValue::Array(old), JsonValue::Array(new)) => {
+                for (i, value) in old.iter().enumerate() {
+                    match new.get(i) {
+                        Some(new_value) => diff.compare(i.to_string(), value, new_value),
+                        None => {
+                            diff.removed.insert(i.to_string(), value.clone());
+                        }
+                    }
+                }
+                for (i, value) in new.iter().enumerate().skip(old.len()) {
+                    diff.added.insert(i.to_string(), value.clone());
+                }
+            }
+            _ => {
+                let mut change = Map::with_capacity(2);
+                change.insert("from".to_string(), self.clone());
+                change.insert("to".to_string(), other.clone());
+                return JsonValue::Object(change);
+            }
+        }
+        diff.into_value()
+    }
+}
+
+impl JsonValue {
+    /// Lists how `other` differs from this value, as a flat list of
+    /// [`Change`]s that is easier to report on than a [`diff`](JsonValue::diff).
+    ///
+    /// Objects and arrays on both sides are compared member by member and
+    /// index by index, as `diff` compares them, so only the innermost
+    /// differences are listed. They come in the order of this value's
+    /// members, followed by anything `other` added.
+    ///
+    /// ```
+    /// use json_parser::{json, Change};
+    ///
+    /// let old = json!({"port": 80, "tags": ["a"]});
+    /// let new = json!({"port": 8080, "tags": ["a", "b"]});
+    /// assert_eq!(
+    ///     old.changes(&new),
+    ///     [
+    ///         Change::Modified { path: "/port".to_string(), old: json!(80), new: json!(8080) },
+    ///         Change::Added { path: "/tags/1".to_string(), value: json!("b") },
+    ///     ]
+    /// );
+    /// ```
+    pub fn changes(&self, other: &JsonValue) -> Vec<Change> {
+        let mut changes = Vec::new();
+        collect_changes(self, other, &mut String::new(), &mut changes);
+        changes
+    }
+}
+
+// Adds the changes from `old` to `new`, which are both at `path`.
+fn collect_changes(old: &JsonValue, new: &JsonValue, path: &mut String, changes: &mut Vec<Change>) {
+    let parent = path.len();
+    let mut child = |path: &mut String, segment: &str, old: Option<&JsonValue>, new: Option<&JsonValue>| {
+        path.push('/');
+        path.push_str(segment);
+        match (old, new) {
+            (Some(old), Some(new)) => collect_changes(old, new, path, changes),
+            (Some(old), None) => changes.push(Change::Removed { path: path.clone(), value: old.clone() }),
+            (None, Some(new)) => changes.push(Change::Added { path: path.clone(), value: new.clone() }),
+            (None, None) => {}
+        }
+        path.truncate(parent);
+    };
+    match (old, new) {
+        _ if old == new => {}
+        (JsonValue::Object(old), JsonValue::Object(new)) => {
+            for (key, value) in old {
+                child(path, &pointer::escape(key), Some(value), new.get(key));
+            }
+            for (key, value) in new {
+                if !old.contains_key(key) {
+                    child(path, &pointer::escape(key), None, Some(value));
+                }
+            }
+        }
+        (JsonValue::Array(old), JsonValue::Array(new)) => {
+            for i in 0..old.len().max(new.len()) {
+                child(path, &i.to_string(), old.get(i), new.get(i));
+            }
+        }
+        _ => changes.push(Change::Modified { path: path.clone(), old: old.clone(), new: new.clone() }),
+    }
+}
+
+#[derive(Default)]
+struct Diff {
+    added: Map,
+    removed: Map,
+    changed: Map,
+}
+
+impl Diff {
+    fn compare(&mut self, key: String, old: &JsonValue, new: &JsonValue) {
+        if old != new {
+            self.changed.insert(key, old.diff(new));
+        }
+    }
+
+    fn into_value(self) -> JsonValue {
+        let sections = [("added", self.added), ("removed", self.removed), ("changed", self.changed)];
+        JsonValue::Object(
+            sections
+                .into_iter()
+                .filter(|(_, members)| !members.is_empty())
+                .map(|(name, members)| (name.to_string(), JsonValue::Object(members)))
+                .collect(),
+        )
+    }
+}
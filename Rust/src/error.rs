@@ -0,0 +1,169 @@
+use std::fmt;
+use std::io;
+
+/// A location in the parser's input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// Byte offset from the start of the input.
+    pub offset: usize,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters from the start of the line.
+    pub column: usize,
+}
+
+impl Position {
+    /// Computes the position of `offset` within `input`.
+    pub(crate) fn at(input: &str, offset: usize) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Position { offset, line: before.matches('\n').count() + 1, column: before[line_start..].chars().count() + 1 }
+    }
+
+    /// Computes the position of `rest`, a suffix of `input` that the parser
+    /// had not consumed yet.
+    pub(crate) fn of_remaining(input: &str, rest: &str) -> Self {
+        Position::at(input, input.len() - rest.len())
+    }
+}
+
+/// One of the size limits in [`ParseOptions`](crate::ParseOptions), as
+/// reported by [`ParseError::LimitExceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// [`max_string_length`](crate::ParseOptions::max_string_length).
+    StringLength,
+    /// [`max_array_length`](crate::ParseOptions::max_array_length).
+    ArrayLength,
+    /// [`max_object_entries`](crate::ParseOptions::max_object_entries).
+    ObjectEntries,
+}
+
+/// The error returned when input cannot be parsed as JSON.
+#[derive(Debug)]
+pub enum ParseError {
+    /// A character that cannot start or continue a value at this point, such
+    /// as a missing comma, an unquoted key or a raw control character inside
+    /// a string.
+    UnexpectedChar { found: char, position: Position },
+    /// The input ended in the middle of a value.
+    UnexpectedEof { position: Position },
+    /// A number does not follow the JSON number grammar, like `01`, `1.` or
+    /// `-`. The position is where the number went wrong.
+    InvalidNumber { position: Position },
+    /// A backslash in a string is not followed by a valid escape, or a
+    /// `\u` escape is an unpaired surrogate.
+    InvalidEscape { position: Position },
+    /// An object key is a bare identifier, like `{name: 1}`, instead of a
+    /// quoted string.
+    UnquotedKey { position: Position },
+    /// A complete value was parsed but was followed by something other than
+    /// whitespace.
+    TrailingData { position: Position },
+    /// Arrays and objects were nested more than `limit` levels deep. The
+    /// position is that of the opening bracket that went over the limit.
+    DepthLimitExceeded { limit: usize, position: Position },
+    /// A string, array or object was larger than the `what` limit allows.
+    /// The position is that of the string, or of the element or member
+    /// that went over the limit.
+    LimitExceeded { what: Limit, limit: usize, position: Position },
+    /// The input was longer than the `limit` bytes that
+    /// [`max_input_bytes`](crate::ParseOptions::max_input_bytes) allows, so
+    /// none of it was parsed. The position is that of the character the
+    /// limit falls in.
+    InputTooLarge { limit: usize, position: Position },
+    /// An integer is outside the range JavaScript represents exactly, when
+    /// [`enforce_js_safe_integers`](crate::ParseOptions::enforce_js_safe_integers)
+    /// is set. The position is that of the number.
+    UnsafeInteger { position: Position },
+    /// An object has a second member named `key`, when
+    /// [`error_on_duplicate_keys`](crate::ParseOptions::error_on_duplicate_keys)
+    /// is set. The position is that of the repeated key.
+    DuplicateKey { key: String, position: Position },
+    /// The input bytes are not valid UTF-8. The position is that of the first
+    /// byte of the offending sequence.
+    InvalidUtf8 { position: Position },
+    /// Reading the input failed before it could be parsed.
+    Io(io::Error),
+}
+
+impl ParseError {
+    /// Where in the input parsing stopped, or `None` if the input could not
+    /// be read at all.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::UnexpectedChar { position, .. }
+            | ParseError::UnexpectedEof { position }
+            | ParseError::InvalidNumber { position }
+            | ParseError::InvalidEscape { position }
+            | ParseError::UnquotedKey { position }
+            | ParseError::TrailingData { position }
+            | ParseError::DepthLimitExceeded { position, .. }
+            | ParseError::LimitExceeded { position, .. }
+            | ParseError::InputTooLarge { position, .. }
+            | ParseError::UnsafeInteger { position }
+            | ParseError::DuplicateKey { position, .. }
+            | ParseError::InvalidUtf8 { position } => Some(*position),
+            ParseError::Io(_) => None,
+        }
+    }
+
+    pub(crate) fn position_mut(&mut self) -> Option<&mut Position> {
+        match self {
+            ParseError::UnexpectedChar { position, .. }
+            | ParseError::UnexpectedEof { position }
+            | ParseError::InvalidNumber { position }
+            | ParseError::InvalidEscape { position }
+            | ParseError::UnquotedKey { position }
+            | ParseError::TrailingData { position }
+            | ParseError::DepthLimitExceeded { position, .. }
+            | ParseError::LimitExceeded { position, .. }
+            | ParseError::InputTooLarge { position, .. }
+            | ParseError::UnsafeInteger { position }
+            | ParseError::DuplicateKey { position, .. }
+            | ParseError::InvalidUtf8 { position } => Some(position),
+            ParseError::Io(_) => None,
+        }
+    }
+}
+
+/// Describes the error, followed by the line and column where it occurred.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedChar { found, .. } => write!(f, "unexpected character {:?}", found)?,
+            ParseError::UnexpectedEof { .. } => f.write_str("unexpected end of input")?,
+            ParseError::InvalidNumber { .. } => f.write_str("invalid number")?,
+            ParseError::InvalidEscape { .. } => f.write_str("invalid escape sequence")?,
+            ParseError::UnquotedKey { .. } => f.write_str("object keys must be quoted strings")?,
+            ParseError::TrailingData { .. } => f.write_str("trailing data after the JSON value")?,
+            ParseError::DepthLimitExceeded { limit, .. } => write!(f, "nesting exceeds the limit of {} levels", limit)?,
+            ParseError::LimitExceeded { what, limit, .. } => {
+                let (container, unit) = match what {
+                    Limit::StringLength => ("string", "bytes"),
+                    Limit::ArrayLength => ("array", "elements"),
+                    Limit::ObjectEntries => ("object", "members"),
+                };
+                write!(f, "{} exceeds the limit of {} {}", container, limit, unit)?
+            }
+            ParseError::InputTooLarge { limit, .. } => write!(f, "input exceeds the limit of {} bytes", limit)?,
+            ParseError::UnsafeInteger { .. } => f.write_str("integer is outside the safe JavaScript range")?,
+            ParseError::DuplicateKey { key, .. } => write!(f, "duplicate key {:?}", key)?,
+            ParseError::InvalidUtf8 { .. } => f.write_str("invalid UTF-8")?,
+            ParseError::Io(e) => return write!(f, "failed to read input: {}", e),
+        }
+        match self.position() {
+            Some(position) => write!(f, " at line {}, column {}", position.line, position.column),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
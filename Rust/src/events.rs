@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+use crate::parser::{Builder, Number, RawStr};
+use crate::JsonValueRef;
+
+/// A step in the walk [`parse_events`](crate::parse_events) makes through a
+/// document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent<'a> {
+    StartObject,
+    /// The key of the member whose value comes next.
+    Key(Cow<'a, str>),
+    EndObject,
+    StartArray,
+    EndArray,
+    /// A scalar: never an `Array` or `Object`, which are reported as start
+    /// and end events instead.
+    Value(JsonValueRef<'a>),
+}
+
+/// Hands each piece of the document to a callback instead of building it.
+pub(crate) struct EventBuilder<F>(pub(crate) RefCell<F>);
+
+impl<'a, F: FnMut(JsonEvent<'a>)> EventBuilder<F> {
+    fn emit(&self, event: JsonEvent<'a>) {
+        (self.0.borrow_mut())(event)
+    }
+}
+
+impl<'a, F: FnMut(JsonEvent<'a>)> Builder<'a> for EventBuilder<F> {
+    type Value = ();
+    type Array = ();
+    type Object = ();
+
+    fn null(&self) {
+        self.emit(JsonEvent::Value(JsonValueRef::Null));
+    }
+
+    fn bool(&self, b: bool) {
+        self.emit(JsonEvent::Value(JsonValueRef::Bool(b)));
+    }
+
+    fn number(&self, lexeme: &'a str) {
+        let value = match Number::from_lexeme(lexeme) {
+            Number::Int(i) => JsonValueRef::Int(i),
+            Number::Float(f) => JsonValueRef::Float(f),
+        };
+        self.emit(JsonEvent::Value(value));
+    }
+
+    fn non_finite(&self, n: f64) {
+        self.emit(JsonEvent::Value(JsonValueRef::Float(n)));
+    }
+
+    fn string(&self, s: RawStr<'a>) {
+        self.emit(JsonEvent::Value(JsonValueRef::Str(s.decode())));
+    }
+
+    fn array(&self) {
+        self.emit(JsonEvent::StartArray);
+    }
+
+    fn push(&self, _: &mut (), _: ()) {}
+
+    fn finish_array(&self, _: ()) {
+        self.emit(JsonEvent::EndArray);
+    }
+
+    fn object(&self) {
+        self.emit(JsonEvent::StartObject);
+    }
+
+    fn key(&self, key: RawStr<'a>) {
+        self.emit(JsonEvent::Key(key.decode()));
+    }
+
+    fn member(&self, _: &mut (), _: RawStr<'a>, _: ()) {}
+
+    fn finish_object(&self, _: ()) {
+        self.emit(JsonEvent::EndObject);
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::pointer::array_index;
+use crate::{JsonValue, Map};
+
+/// The error returned by [`JsonValue::unflatten`] when the paths don't
+/// describe a single consistent structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnflattenError {
+    /// The dotted path where the conflict was found. It is used both for a
+    /// value and as the parent of other paths, or it has both index and
+    /// member children, or its index children leave gaps.
+    pub path: String,
+}
+
+impl fmt::Display for UnflattenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicting paths at {:?}", self.path)
+    }
+}
+
+impl std::error::Error for UnflattenError {}
+
+impl JsonValue {
+    /// Flattens nested arrays and objects into a single map from dotted
+    /// paths to leaf values, such as `address.city` or `scores.0`.
+    ///
+    /// Array elements are keyed by their index. Empty arrays and objects have
+    /// no leaves of their own, so they are kept as values under their path
+    /// rather than disappearing. A scalar at the top level is stored under
+    /// the empty key. Keys that themselves contain a `.` are joined as they
+    /// are, so such paths are ambiguous.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let flat = json!({"a": {"b": 1}, "c": [true, []]}).flatten();
+    /// assert_eq!(flat["a.b"], json!(1));
+    /// assert_eq!(flat["c.0"], json!(true));
+    /// assert_eq!(flat["c.1"], json!([]));
+    /// ```
+    pub fn flatten(&self) -> HashMap<String, JsonValue> {
+        let mut out = HashMap::new();
+        flatten_into(self, &mut String::new(), &mut out);
+        out
+    }
+
+    /// Rebuilds a nested value from the dotted paths that
+    /// [`flatten`](JsonValue::flatten) produces.
+    ///
+    /// Each path is split on `.` and its value is placed at the end of it,
+    /// creating containers along the way. A container whose children are all
+    /// array indices (`0`, `1`, ... with no gaps) becomes an array; any other
+    /// becomes an object with its members sorted by key, since the input
+    /// map has no order. The empty path stands for the whole document.
+    pub fn unflatten(flat: HashMap<String, JsonValue>) -> Result<JsonValue, UnflattenError> {
+        let mut root = Node::Branch(BTreeMap::new());
+        for (path, value) in flat {
+            if path.is_empty() {
+                match &root {
+                    Node::Branch(children) if children.is_empty() => root = Node::Leaf(value),
+                    _ => return Err(UnflattenError { path }),
+                }
+                continue;
+            }
+            root.insert(&path, value)?;
+        }
+        root.build(&mut String::new())
+    }
+}
+
+enum Node {
+    Leaf(JsonValue),
+    Branch(BTreeMap<String, Node>),
+}
+
+impl Node {
+    fn insert(&mut self, path: &str, value: JsonValue) -> Result<(), UnflattenError> {
+        let conflict = |end: usize| UnflattenError { path: path[..end].to_string() };
+        let mut node = self;
+        let mut segments = path.split('.').peekable();
+        // Where the path to `node` ends.
+        let mut end = 0;
+        while let Some(segment) = segments.next() {
+            let Node::Branch(children) = node else {
+                return Err(conflict(end));
+            };
+            end += if end == 0 { segment.len() } else { segment.len() + 1 };
+            if segments.peek().is_none() {
+                if children.contains_key(segment) {
+                    return Err(conflict(end));
+                }
+                children.insert(segment.to_string(), Node::Leaf(value));
+                return Ok(());
+            }
+            node = children.entry(segment.to_string()).or_insert_with(|| Node::Branch(BTreeMap::new()));
+        }
+        Ok(())
+    }
+
+    // `path` holds the dotted path to this node, and is restored before
+    // returning.
+    fn build(self, path: &mut String) -> Result<JsonValue, UnflattenError> {
+        let children = match self {
+            Node::Leaf(value) => return Ok(value),
+            Node::Branch(children) => children,
+        };
+        let mut built = Vec::with_capacity(children.len());
+        for (key, child) in children {
+            let len = path.len();
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(&key);
+            let value = child.build(path)?;
+            path.truncate(len);
+            built.push((key, value));
+        }
+
+        let indices: Vec<_> = built.iter().map(|(key, _)| array_index(key)).collect();
+        if built.is_empty() || indices.iter().all(Option::is_none) {
+            return Ok(JsonValue::Object(built.into_iter().collect::<Map>()));
+        }
+        let mut items = vec![None; built.len()];
+        for (index, (_, value)) in indices.into_iter().zip(built) {
+            match index.and_then(|i| items.get_mut(i)) {
+                Some(slot) => *slot = Some(value),
+                None => return Err(UnflattenError { path: path.clone() }),
+            }
+        }
+        Ok(JsonValue::Array(items.into_iter().map(Option::unwrap).collect()))
+    }
+}
+
+// `path` holds the dotted path to `value`, and is restored before returning.
+fn flatten_into(value: &JsonValue, path: &mut String, out: &mut HashMap<String, JsonValue>) {
+    let mut visit = |key: &str, child: &JsonValue| {
+        let len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(key);
+        flatten_into(child, path, out);
+        path.truncate(len);
+    };
+    match value {
+        JsonValue::Object(members) if !members.is_empty() => {
+            for (key, child) in members {
+                visit(key, child);
+            }
+        }
+        JsonValue::Array(items) if !items.is_empty() => {
+            for (i, child) in items.iter().enumerate() {
+                visit(&i.to_string(), child);
+            }
+        }
+        leaf => {
+            out.insert(path.clone(), leaf.clone());
+        }
+    }
+}
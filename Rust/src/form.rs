@@ -0,0 +1,56 @@
+//! Encoding flat objects as URL query strings.
+
+use std::fmt::Write;
+
+use crate::{JsonValue, TypeError};
+
+impl JsonValue {
+    /// Encodes a flat object as a URL query string, `key=value` pairs joined
+    /// with `&` in member order.
+    ///
+    /// Keys and values are percent-encoded as UTF-8, leaving only ASCII
+    /// letters, digits and `-._~` as they are, so a space becomes `%20`.
+    /// Strings are written without their quotes, numbers and booleans as
+    /// they'd appear in JSON, and `null` as an empty value. A value that
+    /// isn't an object, or has an array or object as a member, is a
+    /// [`TypeError`].
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let query = json!({"q": "rust json", "page": 2, "all": true}).to_query_string().unwrap();
+    /// assert_eq!(query, "q=rust%20json&page=2&all=true");
+    /// ```
+    pub fn to_query_string(&self) -> Result<String, TypeError> {
+        let JsonValue::Object(members) = self else {
+            return Err(TypeError { expected: "an object", found: self.kind() });
+        };
+        let mut out = String::new();
+        for (i, (key, value)) in members.iter().enumerate() {
+            if i > 0 {
+                out.push('&');
+            }
+            percent_encode(&mut out, key);
+            out.push('=');
+            match value {
+                JsonValue::Null => {}
+                JsonValue::Str(s) => percent_encode(&mut out, s),
+                JsonValue::Array(_) | JsonValue::Object(_) => {
+                    return Err(TypeError { expected: "a scalar", found: value.kind() });
+                }
+                scalar => percent_encode(&mut out, &scalar.to_string()),
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn percent_encode(out: &mut String, s: &str) {
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            write!(out, "%{:02X}", byte).unwrap();
+        }
+    }
+}
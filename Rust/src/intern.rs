@@ -0,0 +1,92 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::parser::{Builder, RawStr, ValueBuilder};
+use crate::{JsonValue, Map};
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], but allocates each distinct
+/// object key once and shares it between every object that uses it, for
+/// [`ParseOptions::intern_keys`](crate::ParseOptions::intern_keys).
+#[derive(Default)]
+pub(crate) struct InterningBuilder {
+    keys: RefCell<HashSet<Arc<str>>>,
+}
+
+impl InterningBuilder {
+    fn intern(&self, key: &str) -> Arc<str> {
+        let mut keys = self.keys.borrow_mut();
+        if let Some(shared) = keys.get(key) {
+            return shared.clone();
+        }
+        let shared: Arc<str> = key.into();
+        keys.insert(shared.clone());
+        shared
+    }
+}
+
+impl<'a> Builder<'a> for &InterningBuilder {
+    type Value = JsonValue;
+    type Array = Vec<JsonValue>;
+    type Object = Map;
+
+    fn null(&self) -> JsonValue {
+        ValueBuilder.null()
+    }
+
+    fn bool(&self, b: bool) -> JsonValue {
+        ValueBuilder.bool(b)
+    }
+
+    fn number(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.number(lexeme)
+    }
+
+    fn raw_number(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.raw_number(lexeme)
+    }
+
+    fn big_integer(&self, lexeme: &'a str) -> JsonValue {
+        ValueBuilder.big_integer(lexeme)
+    }
+
+    fn custom_number(&self, lexeme: &'a str, handler: &dyn Fn(&str) -> JsonValue) -> JsonValue {
+        ValueBuilder.custom_number(lexeme, handler)
+    }
+
+    fn non_finite(&self, n: f64) -> JsonValue {
+        ValueBuilder.non_finite(n)
+    }
+
+    fn string(&self, s: RawStr<'a>) -> JsonValue {
+        ValueBuilder.string(s)
+    }
+
+    fn array(&self) -> Vec<JsonValue> {
+        ValueBuilder.array()
+    }
+
+    fn push(&self, array: &mut Vec<JsonValue>, value: JsonValue) {
+        ValueBuilder.push(array, value);
+    }
+
+    fn reserve(&self, array: &mut Vec<JsonValue>, additional: usize) {
+        ValueBuilder.reserve(array, additional);
+    }
+
+    fn finish_array(&self, array: Vec<JsonValue>) -> JsonValue {
+        ValueBuilder.finish_array(array)
+    }
+
+    fn object(&self) -> Map {
+        ValueBuilder.object()
+    }
+
+    fn member(&self, object: &mut Map, key: RawStr<'a>, value: JsonValue) {
+        object.insert_shared(self.intern(&key.decode()), value);
+    }
+
+    fn finish_object(&self, object: Map) -> JsonValue {
+        ValueBuilder.finish_object(object)
+    }
+}
@@ -0,0 +1,378 @@
+//! A small JSON parser built on [nom](https://docs.rs/nom).
+
+mod borrowed;
+mod build;
+mod columns;
+mod convert;
+mod diagnostics;
+mod diff;
+mod error;
+mod events;
+mod flatten;
+mod form;
+mod intern;
+mod macros;
+pub mod map;
+mod merge;
+mod options;
+mod parser;
+mod patch;
+mod pointer;
+mod query;
+mod recover;
+mod ser;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod shape;
+mod shared;
+mod spans;
+mod stats;
+mod tokens;
+mod value;
+mod yaml;
+
+pub use borrowed::JsonValueRef;
+pub use build::{ArrayBuilder, ObjectBuilder};
+pub use columns::ColumnLengthError;
+pub use convert::{ConvertError, FromJson, IntoJson, LookupError};
+pub use diagnostics::Diagnostic;
+pub use diff::Change;
+pub use error::{Limit, ParseError, Position};
+pub use events::JsonEvent;
+pub use flatten::UnflattenError;
+pub use map::Map;
+pub use options::{InvalidUtf8Policy, KeyCase, LineEnding, NumberHandler, ParseOptions, SerializeOptions};
+pub use patch::PatchError;
+pub use pointer::PointerError;
+pub use shape::Shape;
+pub use shared::JsonValueRc;
+pub use spans::{Spanned, SpannedValue};
+pub use stats::ParseStats;
+pub use tokens::Token;
+pub use value::{JsonValue, TypeError};
+
+use std::io::Read;
+
+/// Parses a complete JSON document.
+///
+/// Leading and trailing whitespace is ignored, but the whole input must be
+/// exactly one JSON value: anything else left over after it is reported as
+/// [`ParseError::TrailingData`]. Errors record the [`Position`] where parsing
+/// stopped. An empty or whitespace-only input is reported as
+/// [`ParseError::UnexpectedEof`] at its end.
+///
+/// Object members keep their document order. If an object repeats a key, the
+/// last occurrence wins. Nesting is limited to the default
+/// [`ParseOptions::max_depth`].
+pub fn parse(input: &str) -> Result<JsonValue, ParseError> {
+    parse_with_options(input, &ParseOptions::default())
+}
+
+/// Parses a complete JSON document like [`parse`], with the behaviour
+/// adjusted by `options`.
+pub fn parse_with_options(input: &str, options: &ParseOptions) -> Result<JsonValue, ParseError> {
+    let mut value = if options.intern_keys {
+        parser::parse_document(input, options, &intern::InterningBuilder::default())?
+    } else {
+        parser::parse_document(input, options, parser::ValueBuilder)?
+    };
+    if let Some(case) = options.normalize_keys {
+        value.fold_keys(case);
+    }
+    Ok(value)
+}
+
+/// Checks that `input` is a complete JSON document, without building it.
+///
+/// This accepts and rejects exactly what [`parse`] does, with the same
+/// errors, but allocates nothing for documents nested up to 32 levels deep,
+/// and only a little for deeper ones, so it's the cheaper choice when the
+/// parsed value isn't needed.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parser::parse_document(input, &ParseOptions::default(), parser::Validator)
+}
+
+/// Parses a complete JSON document like [`parse`], borrowing strings from
+/// `input` instead of copying them.
+///
+/// Strings and keys without escapes are returned as slices of `input`; only
+/// those with escapes are decoded into new allocations.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use json_parser::JsonValueRef;
+///
+/// let value = json_parser::parse_borrowed(r#"["plain", "esc\u0061ped"]"#).unwrap();
+/// let JsonValueRef::Array(items) = value else { unreachable!() };
+/// assert!(matches!(&items[0], JsonValueRef::Str(Cow::Borrowed("plain"))));
+/// assert!(matches!(&items[1], JsonValueRef::Str(Cow::Owned(s)) if s == "escaped"));
+/// ```
+pub fn parse_borrowed(input: &str) -> Result<JsonValueRef<'_>, ParseError> {
+    parser::parse_document(input, &ParseOptions::default(), borrowed::RefBuilder)
+}
+
+/// Parses newline-delimited JSON (NDJSON, or JSON Lines): each line that
+/// isn't blank is parsed as a separate document, like [`parse`].
+///
+/// The results are yielded in order, so a malformed line is reported
+/// without stopping the lines after it. Error positions are relative to
+/// the whole of `input`, not to the line. Only the first line may start with
+/// a byte order mark.
+///
+/// ```
+/// let values: Vec<_> = json_parser::parse_lines("{\"id\": 1}\n\n[2]\n").collect();
+/// assert_eq!(values.len(), 2);
+/// assert_eq!(values[1].as_ref().unwrap(), &json_parser::json!([2]));
+/// ```
+pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<JsonValue, ParseError>> + '_ {
+    let mut offset = 0;
+    input.split('\n').enumerate().filter_map(move |(index, line)| {
+        let start = offset;
+        offset += line.len() + 1;
+        if line.trim_matches([' ', '\t', '\r']).is_empty() {
+            return None;
+        }
+        // Only the first line is at the start of the input, where a byte
+        // order mark is allowed.
+        let body = if index == 0 { parser::skip_bom(line) } else { line };
+        let parsed = parser::parse_document_at(line, body, &ParseOptions::default(), parser::ValueBuilder);
+        Some(parsed.map_err(|mut e| {
+            if let Some(position) = e.position_mut() {
+                position.offset += start;
+                position.line += index;
+            }
+            e
+        }))
+    })
+}
+
+/// Parses a stream of JSON values written one after another, like
+/// `{"a":1}[2,3]true`, yielding each in turn until the input runs out.
+///
+/// Whitespace between the values is optional except where two of them would
+/// otherwise run together, such as two numbers. A malformed value is yielded
+/// as an error, which ends the iteration, since there's no telling where the
+/// next value would start. Error positions are relative to the whole of
+/// `input`. A byte order mark is only skipped at the start of `input`, not
+/// between values.
+///
+/// ```
+/// let values: Vec<_> = json_parser::parse_stream("1 2 [3]").map(Result::unwrap).collect();
+/// assert_eq!(values, [json_parser::json!(1), json_parser::json!(2), json_parser::json!([3])]);
+/// ```
+pub fn parse_stream(input: &str) -> impl Iterator<Item = Result<JsonValue, ParseError>> + '_ {
+    let mut rest = Some(parser::skip_bom(input));
+    std::iter::from_fn(move || {
+        let start = parser::skip_whitespace(rest?);
+        if start.is_empty() {
+            rest = None;
+            return None;
+        }
+        match parser::parse_prefix(input, start, &ParseOptions::default(), parser::ValueBuilder) {
+            Ok((value, after)) => {
+                rest = Some(after);
+                Some(Ok(value))
+            }
+            Err(e) => {
+                rest = None;
+                Some(Err(e))
+            }
+        }
+    })
+}
+
+/// Parses a complete JSON document, calling `f` with each [`JsonEvent`] in
+/// document order instead of building a tree.
+///
+/// Nested values are reported between their container's start and end
+/// events, and each object member as a `Key` event followed by the events of
+/// its value. Events are delivered as the parser reaches them, so when the
+/// input turns out to be malformed `f` will already have seen the events
+/// before the error.
+///
+/// ```
+/// use json_parser::{JsonEvent, JsonValueRef};
+///
+/// let mut city = None;
+/// let mut in_city = false;
+/// let input = r#"{"address": {"city": "北京"}, "scores": [90, 85]}"#;
+/// json_parser::parse_events(input, |event| match event {
+///     JsonEvent::Key(key) => in_city = key == "city",
+///     JsonEvent::Value(JsonValueRef::Str(s)) if in_city => city = Some(s),
+///     _ => {}
+/// })
+/// .unwrap();
+/// assert_eq!(city.as_deref(), Some("北京"));
+/// ```
+pub fn parse_events<'a, F: FnMut(JsonEvent<'a>)>(input: &'a str, f: F) -> Result<(), ParseError> {
+    let builder = events::EventBuilder(std::cell::RefCell::new(f));
+    parser::parse_document(input, &ParseOptions::default(), builder)
+}
+
+/// Splits `input` into JSON tokens, each with the byte range it came from,
+/// without checking that they form a valid document.
+///
+/// Whitespace between tokens is skipped. Anything that isn't a token, such
+/// as a malformed number or string, is yielded as an error, which ends the
+/// iteration.
+///
+/// ```
+/// use json_parser::Token;
+///
+/// let tokens: Vec<_> = json_parser::tokenize("[1, true]").map(|token| token.unwrap().value).collect();
+/// assert_eq!(tokens, [Token::LBracket, Token::Number("1"), Token::Comma, Token::Bool(true), Token::RBracket]);
+/// ```
+pub fn tokenize(input: &str) -> impl Iterator<Item = Result<Spanned<Token<'_>>, ParseError>> + '_ {
+    tokens::tokenize(input)
+}
+
+/// Parses a single JSON value from the start of `input` and returns it along
+/// with the rest of the input.
+///
+/// Unlike [`parse`], anything may follow the value, so this can pull one value
+/// out of a larger stream and leave the caller to carry on with the tail.
+/// Whitespace before the value is skipped; whitespace after it is left in the
+/// tail.
+///
+/// ```
+/// let (value, rest) = json_parser::parse_fragment("[1,2]REST").unwrap();
+/// assert_eq!(value, json_parser::json!([1, 2]));
+/// assert_eq!(rest, "REST");
+/// ```
+pub fn parse_fragment(input: &str) -> Result<(JsonValue, &str), ParseError> {
+    parser::parse_prefix(input, parser::skip_bom(input), &ParseOptions::default(), parser::ValueBuilder)
+}
+
+/// Parses a complete JSON document like [`parse`], but carries on past
+/// missing commas between values and trailing commas before a closing
+/// bracket, as an editor would want, returning every error it found.
+///
+/// Those mistakes are repaired and reported, each at its position in
+/// `input`, and the repaired document is returned. Any other error ends the
+/// attempt: it is reported too, and no value is returned.
+///
+/// ```
+/// let (value, errors) = json_parser::parse_recovering("[1 2, 3,]");
+/// assert_eq!(value, Some(json_parser::json!([1, 2, 3])));
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn parse_recovering(input: &str) -> (Option<JsonValue>, Vec<ParseError>) {
+    recover::parse_recovering(input)
+}
+
+/// Parses a complete JSON document like [`parse`], making a best effort at
+/// broken input, such as a line of a log cut off mid-document.
+///
+/// Exactly these repairs are made:
+///
+/// - A trailing comma after the last element of an array or the last member
+///   of an object is ignored, as with
+///   [`allow_trailing_commas`](ParseOptions::allow_trailing_commas).
+/// - Unquoted keys such as `{name: 1}` are accepted, as with
+///   [`allow_unquoted_keys`](ParseOptions::allow_unquoted_keys).
+/// - If the input ends with arrays or objects still open, they're closed,
+///   provided it doesn't end inside a string or where no value could end,
+///   such as just after a key.
+///
+/// Anything else is an error, just as from [`parse`]. Unlike
+/// [`parse_recovering`], which reports each problem it fixes, this only
+/// returns the value.
+///
+/// ```
+/// let value = json_parser::repair(r#"{"a": [1, 2,], b: {"c": true"#).unwrap();
+/// assert_eq!(value, json_parser::json!({"a": [1, 2], "b": {"c": true}}));
+/// ```
+pub fn repair(input: &str) -> Result<JsonValue, ParseError> {
+    recover::parse_repairing(input)
+}
+
+/// Parses a complete JSON document like [`parse`], recording the byte range
+/// of the source that each value came from.
+///
+/// ```
+/// let value = json_parser::parse_spanned(r#"{"age": 30}"#).unwrap();
+/// assert_eq!(value.span, 0..11);
+/// assert_eq!(value.value.get("age").unwrap().span, 8..10);
+/// ```
+pub fn parse_spanned(input: &str) -> Result<Spanned<SpannedValue>, ParseError> {
+    parser::parse_document(input, &ParseOptions::default(), spans::SpanBuilder { input })
+}
+
+/// Parses a complete JSON document like [`parse`], also reporting how large
+/// and deeply nested it is.
+pub fn parse_with_stats(input: &str) -> Result<(JsonValue, ParseStats), ParseError> {
+    let builder = stats::StatsBuilder::default();
+    let value = parser::parse_document(input, &ParseOptions::default(), &builder)?;
+    Ok((value, builder.stats()))
+}
+
+/// Parses a complete JSON document like [`parse_with_options`], also
+/// returning the problems that the warnings enabled in `options` found along
+/// the way, in document order. They don't stop the value from being built;
+/// with no warnings enabled the list is always empty.
+///
+/// ```
+/// use json_parser::{parse_with_diagnostics, Diagnostic, ParseOptions};
+///
+/// let options = ParseOptions { warn_duplicate_keys: true, ..ParseOptions::default() };
+/// let (value, diagnostics) = parse_with_diagnostics(r#"{"a": 1, "a": 2}"#, &options).unwrap();
+/// assert_eq!(value["a"], json_parser::json!(2));
+/// assert_eq!(diagnostics, [Diagnostic::DuplicateKey { path: String::new(), key: "a".to_string() }]);
+/// ```
+pub fn parse_with_diagnostics(input: &str, options: &ParseOptions) -> Result<(JsonValue, Vec<Diagnostic>), ParseError> {
+    if !options.warn_duplicate_keys {
+        return Ok((parse_with_options(input, options)?, Vec::new()));
+    }
+    let builder = diagnostics::DiagnosticsBuilder::default();
+    let mut value = parser::parse_document(input, options, &builder)?;
+    if let Some(case) = options.normalize_keys {
+        value.fold_keys(case);
+    }
+    Ok((value, builder.into_diagnostics()))
+}
+
+/// Parses a complete JSON document from raw bytes, like [`parse`].
+///
+/// The bytes must be UTF-8; the first invalid sequence is reported as
+/// [`ParseError::InvalidUtf8`] at its byte offset. Since everything outside a
+/// string literal has to be ASCII anyway, the input is validated in a single
+/// pass up front and then parsed as a `&str`.
+pub fn parse_bytes(input: &[u8]) -> Result<JsonValue, ParseError> {
+    parse_bytes_with_options(input, &ParseOptions::default())
+}
+
+/// Parses a complete JSON document from raw bytes like [`parse_bytes`],
+/// with the behaviour adjusted by `options`.
+///
+/// With [`InvalidUtf8Policy::Replace`], invalid UTF-8 is replaced before
+/// parsing, so the positions of any errors are offsets into the repaired
+/// text rather than into `input`.
+pub fn parse_bytes_with_options(input: &[u8], options: &ParseOptions) -> Result<JsonValue, ParseError> {
+    if options.on_invalid_utf8 == InvalidUtf8Policy::Replace {
+        return parse_with_options(&String::from_utf8_lossy(input), options);
+    }
+    match std::str::from_utf8(input) {
+        Ok(input) => parse_with_options(input, options),
+        Err(e) => {
+            let valid = std::str::from_utf8(&input[..e.valid_up_to()]).unwrap();
+            Err(ParseError::InvalidUtf8 { position: Position::at(valid, valid.len()) })
+        }
+    }
+}
+
+/// Reads a complete JSON document from `reader` and parses it like
+/// [`parse_bytes`].
+///
+/// The reader is drained into a buffer before parsing. Failures to read are
+/// returned as [`ParseError::Io`].
+pub fn parse_reader<R: Read>(mut reader: R) -> Result<JsonValue, ParseError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(ParseError::Io)?;
+    parse_bytes(&bytes)
+}
+
+/// Parses `input` and writes it back out as compact JSON, dropping all
+/// whitespace between tokens. String contents are preserved exactly.
+pub fn minify(input: &str) -> Result<String, ParseError> {
+    parse(input).map(|value| value.to_string())
+}
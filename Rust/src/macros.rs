@@ -0,0 +1,91 @@
+/// Builds a [`JsonValue`](crate::JsonValue) from JSON-like syntax.
+///
+/// Arrays and objects nest as in JSON, `null` stands for
+/// [`JsonValue::Null`](crate::JsonValue::Null), and any other value is a Rust
+/// expression converted with `JsonValue::from`. Object keys are string
+/// literals or parenthesized expressions that convert into `String`.
+///
+/// ```
+/// use json_parser::{json, parse};
+///
+/// let name = "John";
+/// let value = json!({
+///     "name": name,
+///     "age": 30,
+///     "scores": [90, 85, 100 - 5],
+///     "address": { "city": "北京", "code": null },
+/// });
+/// assert_eq!(value, parse(r#"{"name": "John", "age": 30, "scores": [90, 85, 95],
+///     "address": {"city": "北京", "code": null}}"#).unwrap());
+/// ```
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::JsonValue::Null
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::JsonValue::Array($crate::__json_array!([] $($tt)*))
+    };
+    ({ $($tt:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut members = $crate::Map::new();
+        $crate::__json_object!(members $($tt)*);
+        $crate::JsonValue::Object(members)
+    }};
+    ($other:expr) => {
+        $crate::JsonValue::from($other)
+    };
+}
+
+// Munches array elements one at a time, since an element expression may span
+// several tokens. Literal arrays, objects and `null` are matched before the
+// general expression arms so they are built recursively.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __json_array {
+    ([$($done:expr),*]) => {
+        vec![$($done),*]
+    };
+    ([$($done:expr),*] null $(, $($rest:tt)*)?) => {
+        $crate::__json_array!([$($done,)* $crate::JsonValue::Null] $($($rest)*)?)
+    };
+    ([$($done:expr),*] [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__json_array!([$($done,)* $crate::json!([$($array)*])] $($($rest)*)?)
+    };
+    ([$($done:expr),*] {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__json_array!([$($done,)* $crate::json!({$($object)*})] $($($rest)*)?)
+    };
+    ([$($done:expr),*] $next:expr, $($rest:tt)*) => {
+        $crate::__json_array!([$($done,)* $crate::json!($next)] $($rest)*)
+    };
+    ([$($done:expr),*] $last:expr) => {
+        $crate::__json_array!([$($done,)* $crate::json!($last)])
+    };
+}
+
+// Inserts object members into `$members` one at a time, with the same
+// value handling as `__json_array`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __json_object {
+    ($members:ident) => {};
+    ($members:ident $key:tt : null $(, $($rest:tt)*)?) => {
+        $members.insert(::std::string::String::from($key), $crate::JsonValue::Null);
+        $crate::__json_object!($members $($($rest)*)?);
+    };
+    ($members:ident $key:tt : [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $members.insert(::std::string::String::from($key), $crate::json!([$($array)*]));
+        $crate::__json_object!($members $($($rest)*)?);
+    };
+    ($members:ident $key:tt : {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $members.insert(::std::string::String::from($key), $crate::json!({$($object)*}));
+        $crate::__json_object!($members $($($rest)*)?);
+    };
+    ($members:ident $key:tt : $value:expr, $($rest:tt)*) => {
+        $members.insert(::std::string::String::from($key), $crate::json!($value));
+        $crate::__json_object!($members $($rest)*);
+    };
+    ($members:ident $key:tt : $value:expr) => {
+        $members.insert(::std::string::String::from($key), $crate::json!($value));
+    };
+}
@@ -1,137 +1,135 @@
-use nom::{
-    Parser,
-    branch::alt,
-    bytes::complete::{tag, take_while},
-    character::complete::{char, digit1, multispace0},
-    combinator::{map, opt, recognize},
-    multi::{separated_list0},
-    sequence::{delimited, pair, preceded, separated_pair, terminated},
-    IResult,
-};
-use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
 
+use json_parser::ParseError;
 
-#[derive(Debug)] 
-enum JsonValue {
-    Null,
-    Num(f64),
-    Bool(bool),
-    Str(String),
-    Array(Vec<JsonValue>),
-    Object(HashMap<String, JsonValue>),
-}
+const USAGE: &str = "\
+usage: JsonParser [--pretty | --minify | --validate] [--indent N | --indent tab] [FILE]
 
-fn parse_null(s: &str) -> IResult<&str, JsonValue> {
-    map(tag("null"), |_| JsonValue::Null).parse(s)
-}
+Reads a JSON document from FILE, or from standard input if FILE is missing
+or `-`, and writes it back out.
 
-fn parse_bool(s: &str) -> IResult<&str, JsonValue> {
-    alt((
-        map(tag("true"), |_| JsonValue::Bool(true)),
-        map(tag("false"), |_| JsonValue::Bool(false)),
-    )).parse(s)
-}
+  --pretty    indent the output (the default)
+  --minify    write the output without any whitespace
+  --validate  only check the input, writing nothing
+  --indent    indent pretty output by N spaces, up to 16, or by one tab
+              (default 2)
+  -h, --help  print this message and exit
+";
 
-fn parse_num(s: &str) -> IResult<&str, JsonValue> {
-    map(recognize(pair(opt(char('-')), digit1)), |s: &str| {
-        JsonValue::Num(s.parse().unwrap())
-    }).parse(s)
-}
+// The widest `--indent` accepted, well past any sensible style but small
+// enough that a typo can't turn into gigabytes of spaces.
+const MAX_INDENT: usize = 16;
 
-fn parse_str(s: &str) -> IResult<&str, JsonValue> {
-    map(
-        delimited(
-            char('"'),
-            take_while(|c| c != '"'),
-            char('"'),
-        ),
-        |s: &str| JsonValue::Str(s.to_string()),
-    ).parse(s)
+#[derive(Clone, Copy)]
+enum Mode {
+    Pretty,
+    Minify,
+    Validate,
 }
 
-fn parse_array(s: &str) -> IResult<&str, JsonValue> {
-    map(
-        delimited(
-            char('['),
-            separated_list0(
-                preceded(multispace0, char(',')),
-                preceded(multispace0, parse_value),
-            ),
-            char(']'),
-        ),
-        |vec| JsonValue::Array(vec),
-    ).parse(s)
+struct Args {
+    help: bool,
+    mode: Mode,
+    indent: String,
+    path: Option<String>,
 }
 
-fn parse_value(s: &str) -> IResult<&str, JsonValue> {
-    preceded(
-        multispace0,
-        alt((
-            parse_str,
-            parse_num,
-            parse_bool,
-            parse_null,
-            parse_array,
-            parse_object,
-        )),
-    ).parse(s)
+fn parse_indent(value: Option<String>) -> Result<String, String> {
+    match value.as_deref() {
+        None => Err("`--indent` needs a value".to_string()),
+        Some("tab") => Ok("\t".to_string()),
+        Some(n) => match n.parse() {
+            Ok(n) if n <= MAX_INDENT => Ok(" ".repeat(n)),
+            Ok(_) => Err(format!("invalid indent `{}`: at most {} spaces are allowed", n, MAX_INDENT)),
+            Err(_) => Err(format!("invalid indent `{}`: expected a number of spaces or `tab`", n)),
+        },
+    }
 }
 
-fn parse_pair(s: &str) -> IResult<&str, (JsonValue, JsonValue)> {
-    separated_pair(
-        preceded(multispace0, parse_str),
-        preceded(multispace0, char(':')),
-        preceded(multispace0, parse_value),
-    ).parse(s)
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args { help: false, mode: Mode::Pretty, indent: "  ".to_string(), path: None };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => parsed.help = true,
+            "--pretty" => parsed.mode = Mode::Pretty,
+            "--minify" => parsed.mode = Mode::Minify,
+            "--validate" => parsed.mode = Mode::Validate,
+            "--indent" => parsed.indent = parse_indent(args.next())?,
+            "-" => parsed.path = None,
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}`", flag)),
+            path if parsed.path.is_none() => parsed.path = Some(path.to_string()),
+            _ => return Err("only one input file may be given".to_string()),
+        }
+    }
+    Ok(parsed)
 }
 
-fn parse_object(s: &str) -> IResult<&str, JsonValue> {
-    map(
-        delimited(
-            char('{'),
-            separated_list0(
-                preceded(multispace0, char(',')),
-                preceded(multispace0, parse_pair),
-            ),
-            preceded(multispace0, char('}')),
-        ),
-        |pairs| JsonValue::Object(pairs.into_iter().map(|(k, v)| {
-            if let JsonValue::Str(k) = k {
-                return (k, v)
-            }
-            panic!("key must be a string")
-        }).collect()),
-    ).parse(s)
+fn read_input(path: Option<&str>) -> Result<Vec<u8>, ParseError> {
+    match path {
+        Some(path) => fs::read(path).map_err(ParseError::Io),
+        None => {
+            let mut bytes = Vec::new();
+            io::stdin().lock().read_to_end(&mut bytes).map_err(ParseError::Io)?;
+            Ok(bytes)
+        }
+    }
 }
 
-fn parse_json(s: &str) -> IResult<&str, JsonValue> {
-    terminated(parse_value, multispace0).parse(s)
+// Checks the input without building it, for `--validate`.
+fn validate(input: &[u8]) -> Result<(), ParseError> {
+    match std::str::from_utf8(input) {
+        Ok(input) => json_parser::validate(input),
+        // Parsing reports where the invalid UTF-8 starts.
+        Err(_) => json_parser::parse_bytes(input).map(drop),
+    }
 }
 
-fn main() {
+fn report(path: Option<&str>, e: ParseError) -> ExitCode {
+    eprintln!("{}: {}", path.unwrap_or("<stdin>"), e);
+    ExitCode::FAILURE
+}
 
-    println!("{:?}", parse_null("null"));
-    println!("{:?}", parse_bool("true"));
-    println!("{:?}", parse_num("123"));
-    println!("{:?}", parse_str("hello"));
-    println!("{:?}", parse_array("[1,2,3]"));
-    println!("{:?}", parse_object("{\"name\": \"John\", \"age\": 30}"));
-    let json_str = r##"
-        {
-            "nickname": "张三",
-            "age": 30,
-            "is_teacher": false,
-            "scores": [90, 85, 95],
-            "address": {
-                "city": "北京",
-                "street": "中关村大街",
-                "code": [200, 2000]
-            }
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprint!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
         }
-    "##;
+    };
+    if args.help {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
 
-    match parse_json(json_str) {
-        Ok((_, json)) => println!("{:#?}", json),
-        Err(e) => println!("Error: {:?}", e),
+    let input = match read_input(args.path.as_deref()) {
+        Ok(input) => input,
+        Err(e) => return report(args.path.as_deref(), e),
+    };
+    // How to indent the output, if at all.
+    let indent = match args.mode {
+        Mode::Validate => {
+            return match validate(&input) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => report(args.path.as_deref(), e),
+            };
+        }
+        Mode::Pretty => Some(&args.indent),
+        Mode::Minify => None,
+    };
+    let value = match json_parser::parse_bytes(&input) {
+        Ok(value) => value,
+        Err(e) => return report(args.path.as_deref(), e),
+    };
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let written = match indent {
+        Some(indent) => value.write_pretty_to(&mut out, indent),
+        None => value.write_to(&mut out),
+    };
+    if written.and_then(|()| writeln!(out)).and_then(|()| out.flush()).is_err() {
+        return ExitCode::FAILURE;
     }
+    ExitCode::SUCCESS
 }
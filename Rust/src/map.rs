@@ -0,0 +1,258 @@
+//! The ordered map backing [`JsonValue::Object`].
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+use std::sync::Arc;
+
+use crate::{JsonValue, KeyCase};
+
+/// The members of a JSON object, kept in insertion order.
+///
+/// Lookups go through a hash index, so they cost the same as with a
+/// `HashMap`, while iteration (and therefore serialization) visits members in
+/// the order they were first inserted. Each key is allocated once and shared
+/// between the member list and the index.
+#[derive(Clone, Default)]
+pub struct Map {
+    entries: Vec<(Arc<str>, JsonValue)>,
+    index: HashMap<Arc<str>, usize>,
+}
+
+impl Map {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Map::default()
+    }
+
+    /// Creates an empty map with room for `capacity` members.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Map { entries: Vec::with_capacity(capacity), index: HashMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no members.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value stored under `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+        self.index.get(key).map(|&i| &mut self.entries[i].1)
+    }
+
+    /// Returns `true` if the map has a member named `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Inserts a member, returning the value it displaced.
+    ///
+    /// Replacing an existing key keeps its original position; a new key is
+    /// appended.
+    pub fn insert(&mut self, key: String, value: JsonValue) -> Option<JsonValue> {
+        if let Some(&i) = self.index.get(key.as_str()) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.append(key.into(), value);
+        None
+    }
+
+    /// Inserts a member like `insert`, with a key that may already be
+    /// shared with other maps.
+    pub(crate) fn insert_shared(&mut self, key: Arc<str>, value: JsonValue) -> Option<JsonValue> {
+        if let Some(&i) = self.index.get(&key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.append(key, value);
+        None
+    }
+
+    // The heap memory behind the member list and the index, each key's
+    // allocation with its reference counts, and the members' own values.
+    pub(crate) fn estimated_heap_size(&self) -> usize {
+        let entries = self.entries.capacity() * std::mem::size_of::<(Arc<str>, JsonValue)>();
+        // The standard hash table keeps a control byte per bucket besides
+        // the bucket itself.
+        let index = self.index.capacity() * (std::mem::size_of::<(Arc<str>, usize)>() + 1);
+        let members: usize = self
+            .entries
+            .iter()
+            .map(|(key, value)| 2 * std::mem::size_of::<usize>() + key.len() + value.estimated_heap_size())
+            .sum();
+        entries + index + members
+    }
+
+    // Folds the keys of these members and every object nested in them.
+    // Keys that fold to the same one merge as `insert` would merge them.
+    pub(crate) fn fold_keys(&mut self, case: KeyCase) {
+        let entries = std::mem::take(&mut self.entries);
+        self.index.clear();
+        for (key, mut value) in entries {
+            value.fold_keys(case);
+            let folded = case.fold(&key);
+            // Keys that don't change stay shared, as `intern_keys` left them.
+            let key = if *folded == *key { key } else { folded.into() };
+            self.insert_shared(key, value);
+        }
+    }
+
+    fn append(&mut self, key: Arc<str>, value: JsonValue) {
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+    }
+
+    /// Removes the member named `key` and returns its value.
+    ///
+    /// Later members shift down to close the gap, so the relative order of
+    /// the remaining members is unchanged.
+    pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        for (key, _) in &self.entries[i..] {
+            *self.index.get_mut(key).unwrap() -= 1;
+        }
+        Some(value)
+    }
+
+    /// Iterates over the members in order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.entries.iter())
+    }
+
+    /// Iterates over the members in order, with mutable access to the values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut JsonValue)> {
+        self.entries.iter_mut().map(|(key, value)| (&**key, value))
+    }
+
+    /// Iterates over the keys in order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| &**key)
+    }
+
+    /// Iterates over the values in order.
+    pub fn values(&self) -> impl Iterator<Item = &JsonValue> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    /// Iterates mutably over the values in order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut JsonValue> {
+        self.entries.iter_mut().map(|(_, value)| value)
+    }
+}
+
+/// Maps compare equal when they have the same members, in any order.
+impl PartialEq for Map {
+    fn eq(&self, other: &Map) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Eq for Map {}
+
+/// Hashes the members independently of their order, as equality ignores it:
+/// each member is hashed on its own and the results are combined with XOR.
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let members = self.iter().fold(0, |combined, member| {
+            let mut hasher = DefaultHasher::new();
+            member.hash(&mut hasher);
+            combined ^ hasher.finish()
+        });
+        self.len().hash(state);
+        members.hash(state);
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Looks up a member by key, panicking if it is missing, like `HashMap`.
+impl Index<&str> for Map {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("no member named {:?}", key),
+        }
+    }
+}
+
+impl FromIterator<(String, JsonValue)> for Map {
+    fn from_iter<I: IntoIterator<Item = (String, JsonValue)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<(String, JsonValue)> for Map {
+    fn extend<I: IntoIterator<Item = (String, JsonValue)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// Borrowing iterator over the members of a [`Map`], in order.
+pub struct Iter<'a>(std::slice::Iter<'a, (Arc<str>, JsonValue)>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (&**key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a str, &'a JsonValue);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Owning iterator over the members of a [`Map`], in order.
+pub struct IntoIter(std::vec::IntoIter<(Arc<str>, JsonValue)>);
+
+impl Iterator for IntoIter {
+    type Item = (String, JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (String::from(&*key), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, JsonValue);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter(self.entries.into_iter())
+    }
+}
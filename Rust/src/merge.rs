@@ -0,0 +1,64 @@
+use crate::{JsonValue, Map};
+
+impl JsonValue {
+    /// Applies an [RFC 7386](https://www.rfc-editor.org/rfc/rfc7386) JSON
+    /// Merge Patch to this value.
+    ///
+    /// If `patch` is an object, each of its members is merged into the
+    /// corresponding member here, recursively: a `null` member deletes the
+    /// key (a no-op if it is absent), and anything else is merged into the
+    /// existing value or added. If this value isn't an object it is replaced
+    /// by an empty one first. A `patch` that isn't an object replaces this
+    /// value wholesale.
+    pub fn merge_patch(&mut self, patch: &JsonValue) {
+        let JsonValue::Object(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(Map::new());
+        }
+        let JsonValue::Object(members) = self else { unreachable!() };
+        for (key, value) in patch {
+            if let JsonValue::Null = value {
+                members.remove(key);
+            } else if let Some(target) = members.get_mut(key) {
+                target.merge_patch(value);
+            } else {
+                let mut target = JsonValue::Null;
+                target.merge_patch(value);
+                members.insert(key.to_string(), target);
+            }
+        }
+    }
+}
+
+impl JsonValue {
+    /// Deep-merges `other` into this value.
+    ///
+    /// Objects are merged member by member, recursing into members present on
+    /// both sides. When both sides are arrays, `other`'s elements are appended
+    /// if `concat_arrays` is set and replace this array otherwise. In every
+    /// other case, including a scalar meeting an object, `other` wins.
+    ///
+    /// Unlike [`merge_patch`](JsonValue::merge_patch), `null` in `other` has
+    /// no special meaning and is merged like any other value.
+    pub fn merge(&mut self, other: &JsonValue, concat_arrays: bool) {
+        match (self, other) {
+            (JsonValue::Object(members), JsonValue::Object(other)) => {
+                for (key, value) in other {
+                    match members.get_mut(key) {
+                        Some(target) => target.merge(value, concat_arrays),
+                        None => {
+                            members.insert(key.to_string(), value.clone());
+                        }
+                    }
+                }
+            }
+            (JsonValue::Array(items), JsonValue::Array(other)) if concat_arrays => {
+                items.extend(other.iter().cloned());
+            }
+            (target, other) => *target = other.clone(),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::JsonValue;
+
+/// Settings that control how [`parse_with_options`](crate::parse_with_options)
+/// reads a document.
+///
+/// Construct one with struct update syntax to change only what you need:
+///
+/// ```
+/// use json_parser::ParseOptions;
+///
+/// let options = ParseOptions { max_depth: 16, ..ParseOptions::default() };
+/// ```
+#[derive(Clone)]
+pub struct ParseOptions {
+    /// How many arrays and objects may be nested inside each other. Deeper
+    /// input fails with [`ParseError::DepthLimitExceeded`](crate::ParseError::DepthLimitExceeded).
+    /// The parser itself doesn't recurse, so any limit is safe to parse with,
+    /// but dropping, comparing or serializing a [`JsonValue`](crate::JsonValue)
+    /// does, so very deep values can still overflow the stack there.
+    /// Defaults to 128.
+    pub max_depth: usize,
+    /// The longest a string or key may be, in bytes of its source text
+    /// between the quotes, so escapes count as written. Longer ones fail
+    /// with [`ParseError::LimitExceeded`](crate::ParseError::LimitExceeded),
+    /// as do arrays and objects over the next two limits. Unlimited by
+    /// default.
+    pub max_string_length: usize,
+    /// The most elements an array may have. Unlimited by default.
+    pub max_array_length: usize,
+    /// The most members an object may have, counting repeated keys each
+    /// time. Unlimited by default.
+    pub max_object_entries: usize,
+    /// The longest the whole input may be, in bytes. Longer input fails with
+    /// [`ParseError::InputTooLarge`](crate::ParseError::InputTooLarge) before
+    /// any of it is parsed, so nothing is built only to be thrown away.
+    /// `None`, the default, allows any length.
+    pub max_input_bytes: Option<usize>,
+    /// Accept `// line` and `/* block */` comments wherever whitespace is
+    /// allowed, as in JSONC config files. Off by default.
+    pub allow_comments: bool,
+    /// Accept a single trailing comma after the last element of an array or
+    /// the last member of an object, as in `[1, 2,]`. Off by default.
+    pub allow_trailing_commas: bool,
+    /// Accept the non-standard `NaN`, `Infinity` and `-Infinity` tokens as
+    /// [`JsonValue::Float`](crate::JsonValue::Float) values. Off by default.
+    /// Serializing these values produces `null`, since JSON itself has no
+    /// way to write them.
+    pub allow_non_finite: bool,
+    /// Accept JSON5-style unquoted object keys such as `{name: 1}`. A bare
+    /// key is an identifier: letters, digits, `_` and `$`, not starting with
+    /// a digit. Off by default, when such keys fail with
+    /// [`ParseError::UnquotedKey`](crate::ParseError::UnquotedKey).
+    pub allow_unquoted_keys: bool,
+    /// Accept strings, keys included, delimited by single quotes, as in
+    /// `'hello'`. Inside them a `"` needs no escape and `\'` stands for a
+    /// single quote. Off by default.
+    pub allow_single_quotes: bool,
+    /// Accept the JavaScript escapes `\xXX`, for the character with that
+    /// two-digit hex code, and `\0`, for NUL, in strings and keys. Off by
+    /// default, when they fail with
+    /// [`ParseError::InvalidEscape`](crate::ParseError::InvalidEscape).
+    pub lenient_escapes: bool,
+    /// Keep every number exactly as written, as a
+    /// [`JsonValue::RawNum`](crate::JsonValue::RawNum), instead of converting
+    /// it to an `Int` or `Float`. Nothing is lost to `f64` rounding, and
+    /// serializing writes the original text back out. Off by default.
+    pub preserve_number_strings: bool,
+    /// Keep integers too large for an `i64` exactly, as a
+    /// [`JsonValue::BigInt`](crate::JsonValue::BigInt) holding their
+    /// digits, instead of rounding them to a `Float`. Integers that fit are
+    /// still `Int`s, and numbers with a fraction or exponent still `Float`s.
+    /// Off by default.
+    pub big_integers: bool,
+    /// Turn every number into whatever this function returns for its text,
+    /// as written in the input, so that numbers can be kept in a
+    /// representation of your own, such as a decimal type's string form in
+    /// a `RawNum`. It takes precedence over the two options above. It can
+    /// be any closure, capturing state included, and is shared rather than
+    /// copied when the options are cloned. `None` by default.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use json_parser::{parse_with_options, JsonValue, ParseOptions};
+    ///
+    /// let options = ParseOptions {
+    ///     number_handler: Some(Arc::new(|n| JsonValue::Str(n.to_string()))),
+    ///     ..ParseOptions::default()
+    /// };
+    /// assert_eq!(parse_with_options("[1.50]", &options).unwrap(), json_parser::json!(["1.50"]));
+    /// ```
+    pub number_handler: Option<NumberHandler>,
+    /// Fail with [`ParseError::UnsafeInteger`](crate::ParseError::UnsafeInteger)
+    /// on integers that JavaScript can't represent exactly, those beyond
+    /// ±(2^53 - 1), for documents a browser will read. Only numbers written
+    /// without a fraction or exponent count as integers here. Off by default.
+    pub enforce_js_safe_integers: bool,
+    /// Reject documents whose root is a scalar, such as a bare `42`, as the
+    /// obsolete RFC 4627 did. Off by default, following RFC 8259, which
+    /// allows any value at the root.
+    pub require_container_root: bool,
+    /// Report every repeated object key as a
+    /// [`Diagnostic::DuplicateKey`](crate::Diagnostic::DuplicateKey) from
+    /// [`parse_with_diagnostics`](crate::parse_with_diagnostics). The
+    /// document still parses, with the last value winning as usual. The
+    /// other parse functions ignore it. Off by default.
+    pub warn_duplicate_keys: bool,
+    /// Fail with [`ParseError::DuplicateKey`](crate::ParseError::DuplicateKey)
+    /// when an object has two members with the same key, instead of
+    /// keeping the last. Off by default.
+    pub error_on_duplicate_keys: bool,
+    /// Allocate each distinct object key once and share it between all the
+    /// objects that use it, which saves memory when many objects have the
+    /// same keys, as in an array of records. The resulting value is the
+    /// same either way. Off by default. This is done by
+    /// [`parse_with_options`](crate::parse_with_options), but not while
+    /// checking for duplicate keys.
+    pub intern_keys: bool,
+    /// Fold every object key to lower or upper case, for matching keys
+    /// case-insensitively. Keys that fold to the same one are treated as
+    /// repeats of it, so the last value wins, in the place of the first. The
+    /// duplicate key checks above still see the keys as written. `None`, the
+    /// default, leaves keys alone.
+    pub normalize_keys: Option<KeyCase>,
+    /// What [`parse_bytes_with_options`](crate::parse_bytes_with_options)
+    /// does with bytes that aren't valid UTF-8. By default they're an error.
+    pub on_invalid_utf8: InvalidUtf8Policy,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            max_depth: 128,
+            max_string_length: usize::MAX,
+            max_array_length: usize::MAX,
+            max_object_entries: usize::MAX,
+            max_input_bytes: None,
+            allow_comments: false,
+            allow_trailing_commas: false,
+            allow_non_finite: false,
+            allow_unquoted_keys: false,
+            allow_single_quotes: false,
+            lenient_escapes: false,
+            preserve_number_strings: false,
+            big_integers: false,
+            number_handler: None,
+            enforce_js_safe_integers: false,
+            require_container_root: false,
+            warn_duplicate_keys: false,
+            error_on_duplicate_keys: false,
+            intern_keys: false,
+            normalize_keys: None,
+            on_invalid_utf8: InvalidUtf8Policy::Error,
+        }
+    }
+}
+
+// Written out by hand for the sake of `number_handler`.
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("max_depth", &self.max_depth)
+            .field("max_string_length", &self.max_string_length)
+            .field("max_array_length", &self.max_array_length)
+            .field("max_object_entries", &self.max_object_entries)
+            .field("max_input_bytes", &self.max_input_bytes)
+            .field("allow_comments", &self.allow_comments)
+            .field("allow_trailing_commas", &self.allow_trailing_commas)
+            .field("allow_non_finite", &self.allow_non_finite)
+            .field("allow_unquoted_keys", &self.allow_unquoted_keys)
+            .field("allow_single_quotes", &self.allow_single_quotes)
+            .field("lenient_escapes", &self.lenient_escapes)
+            .field("preserve_number_strings", &self.preserve_number_strings)
+            .field("big_integers", &self.big_integers)
+            .field("number_handler", &self.number_handler.as_ref().map(|_| format_args!("<fn>")))
+            .field("enforce_js_safe_integers", &self.enforce_js_safe_integers)
+            .field("require_container_root", &self.require_container_root)
+            .field("warn_duplicate_keys", &self.warn_duplicate_keys)
+            .field("error_on_duplicate_keys", &self.error_on_duplicate_keys)
+            .field("intern_keys", &self.intern_keys)
+            .field("normalize_keys", &self.normalize_keys)
+            .field("on_invalid_utf8", &self.on_invalid_utf8)
+            .finish()
+    }
+}
+
+/// A function that [`ParseOptions::number_handler`] turns numbers into
+/// values with.
+pub type NumberHandler = Arc<dyn Fn(&str) -> JsonValue + Send + Sync>;
+
+/// The case [`ParseOptions::normalize_keys`] folds keys to, as
+/// [`str::to_lowercase`] and [`str::to_uppercase`] do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `"Name"` becomes `"name"`.
+    Lower,
+    /// `"Name"` becomes `"NAME"`.
+    Upper,
+}
+
+impl KeyCase {
+    pub(crate) fn fold(self, key: &str) -> String {
+        match self {
+            KeyCase::Lower => key.to_lowercase(),
+            KeyCase::Upper => key.to_uppercase(),
+        }
+    }
+}
+
+/// How to treat input bytes that aren't valid UTF-8, for
+/// [`ParseOptions::on_invalid_utf8`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidUtf8Policy {
+    /// Fail with [`ParseError::InvalidUtf8`](crate::ParseError::InvalidUtf8)
+    /// at the first invalid sequence.
+    #[default]
+    Error,
+    /// Replace each invalid sequence with U+FFFD REPLACEMENT CHARACTER and
+    /// carry on, as [`String::from_utf8_lossy`] does. Inside a string the
+    /// replacement becomes part of its value; anywhere else it's a syntax
+    /// error like any other stray character.
+    Replace,
+}
+
+/// Settings that control how
+/// [`to_string_with_options`](crate::JsonValue::to_string_with_options) and
+/// [`to_string_indented_with_options`](crate::JsonValue::to_string_indented_with_options)
+/// write a value. The defaults produce the same output as `to_string` and
+/// `to_string_indented`.
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    /// Write every `/` in a string as `\/`, so that the output can't close
+    /// an HTML `<script>` element it's embedded in. Off by default.
+    pub escape_forward_slashes: bool,
+    /// Write every non-ASCII character in a string as a `\uXXXX` escape,
+    /// using a surrogate pair outside the Basic Multilingual Plane, so the
+    /// output is plain ASCII. Off by default, when such characters are
+    /// written as UTF-8.
+    pub ascii_only: bool,
+    /// Write the members of every object in ascending order of their keys,
+    /// compared as strings, rather than in insertion order. Off by default.
+    pub sort_keys: bool,
+    /// Write floats rounded to exactly this many digits after the decimal
+    /// point, as `3.14` for `3.14159` with `Some(2)`. This is for display
+    /// and loses precision: the output needn't parse back to the same
+    /// value, and with `Some(0)` whole floats come back as integers. `None`,
+    /// the default, writes the shortest digits that round-trip exactly.
+    pub float_precision: Option<usize>,
+    /// What ends each line of indented output. Compact output has no line
+    /// breaks, so it's unaffected.
+    pub line_ending: LineEnding,
+}
+
+/// The line break written by indented output, for
+/// [`SerializeOptions::line_ending`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`, as on Unix.
+    #[default]
+    Lf,
+    /// `\r\n`, as on Windows.
+    Crlf,
+}
@@ -0,0 +1,920 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till, take_until, take_while, take_while1, take_while_m_n},
+    character::complete::{char, digit1, one_of},
+    combinator::{cut, map, map_res, not, opt, peek, recognize, value},
+    multi::{fold_many0, many0_count},
+    sequence::{pair, preceded, terminated},
+    IResult, Parser,
+};
+
+use crate::{JsonValue, Limit, Map, ParseError, ParseOptions, Position};
+
+/// Why the parser stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorKind {
+    Syntax,
+    InvalidNumber,
+    InvalidEscape,
+    UnquotedKey,
+    DepthLimitExceeded,
+    LimitExceeded(Limit),
+    UnsafeInteger,
+    DuplicateKey,
+}
+
+/// The nom error type used by the grammar, recording where parsing stopped
+/// and why.
+#[derive(Debug)]
+pub(crate) struct Error<'a> {
+    input: &'a str,
+    kind: ErrorKind,
+}
+
+impl<'a> Error<'a> {
+    fn new(input: &'a str, kind: ErrorKind) -> Self {
+        Error { input, kind }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, _: nom::error::ErrorKind) -> Self {
+        Error::new(input, ErrorKind::Syntax)
+    }
+
+    fn append(_: &'a str, _: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a, E> nom::error::FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, _: nom::error::ErrorKind, _: E) -> Self {
+        Error::new(input, ErrorKind::Syntax)
+    }
+}
+
+type PResult<'a, O> = IResult<&'a str, O, Error<'a>>;
+
+// Reports syntax failures inside `f`, which only happen once it has
+// committed with `cut`, as `kind` instead.
+fn fail_as<'a, O>(
+    kind: ErrorKind,
+    mut f: impl Parser<&'a str, Output = O, Error = Error<'a>>,
+) -> impl Parser<&'a str, Output = O, Error = Error<'a>> {
+    move |s| match f.parse(s) {
+        Err(nom::Err::Failure(e)) if e.kind == ErrorKind::Syntax => Err(nom::Err::Failure(Error::new(e.input, kind))),
+        result => result,
+    }
+}
+
+/// Assembles the values the grammar recognizes into a document.
+///
+/// The parser is generic over this, so the same grammar can build an owned
+/// [`JsonValue`], a [`JsonValueRef`](crate::JsonValueRef) borrowing from the
+/// input, or nothing at all. Containers are built incrementally: `array` and
+/// `object` start one, each element or member is added as soon as it has
+/// been parsed, and `finish_*` turns the result into a value.
+pub(crate) trait Builder<'a> {
+    type Value;
+    type Array;
+    type Object;
+
+    fn null(&self) -> Self::Value;
+    fn bool(&self, b: bool) -> Self::Value;
+    /// A number, given as its lexeme, which is known to follow the grammar.
+    fn number(&self, lexeme: &'a str) -> Self::Value;
+    /// A number to be kept as its lexeme, when `preserve_number_strings` is
+    /// set. Builders without a way to do that treat it like any other.
+    fn raw_number(&self, lexeme: &'a str) -> Self::Value {
+        self.number(lexeme)
+    }
+    /// An integer too large for `i64`, when `big_integers` is set. Builders
+    /// without a way to keep it exact treat it like any other number.
+    fn big_integer(&self, lexeme: &'a str) -> Self::Value {
+        self.number(lexeme)
+    }
+    /// A number to be turned into a value by `handler`, when `number_handler`
+    /// is set. Builders that don't build a [`JsonValue`] treat it like any
+    /// other.
+    fn custom_number(&self, lexeme: &'a str, _handler: &dyn Fn(&str) -> JsonValue) -> Self::Value {
+        self.number(lexeme)
+    }
+    /// `NaN` or an infinity, which only parse when `allow_non_finite` is set.
+    fn non_finite(&self, n: f64) -> Self::Value;
+    fn string(&self, s: RawStr<'a>) -> Self::Value;
+
+    fn array(&self) -> Self::Array;
+    fn push(&self, array: &mut Self::Array, value: Self::Value);
+    /// Room for about `additional` more items, expected once `array` has
+    /// grown large. Builders that don't store items ignore it.
+    fn reserve(&self, _array: &mut Self::Array, _additional: usize) {}
+    fn finish_array(&self, array: Self::Array) -> Self::Value;
+
+    fn object(&self) -> Self::Object;
+    /// Called with each key as soon as it's parsed, before its value.
+    fn key(&self, _key: RawStr<'a>) {}
+    fn member(&self, object: &mut Self::Object, key: RawStr<'a>, value: Self::Value);
+    fn finish_object(&self, object: Self::Object) -> Self::Value;
+
+    /// Called with each complete value along with the input that remained
+    /// at its first character and just after its last, before the value is
+    /// added to its container. Builders that don't track positions pass the
+    /// value through.
+    fn span(&self, value: Self::Value, _start: &'a str, _end: &'a str) -> Self::Value {
+        value
+    }
+}
+
+/// Builds the owned [`JsonValue`] tree that [`parse`](crate::parse) returns.
+pub(crate) struct ValueBuilder;
+
+impl<'a> Builder<'a> for ValueBuilder {
+    type Value = JsonValue;
+    type Array = Vec<JsonValue>;
+    type Object = Map;
+
+    fn null(&self) -> JsonValue {
+        JsonValue::Null
+    }
+
+    fn bool(&self, b: bool) -> JsonValue {
+        JsonValue::Bool(b)
+    }
+
+    fn number(&self, lexeme: &'a str) -> JsonValue {
+        match Number::from_lexeme(lexeme) {
+            Number::Int(i) => JsonValue::Int(i),
+            Number::Float(f) => JsonValue::Float(f),
+        }
+    }
+
+    fn raw_number(&self, lexeme: &'a str) -> JsonValue {
+        JsonValue::RawNum(lexeme.to_string())
+    }
+
+    fn big_integer(&self, lexeme: &'a str) -> JsonValue {
+        JsonValue::BigInt(lexeme.to_string())
+    }
+
+    fn custom_number(&self, lexeme: &'a str, handler: &dyn Fn(&str) -> JsonValue) -> JsonValue {
+        handler(lexeme)
+    }
+
+    fn non_finite(&self, n: f64) -> JsonValue {
+        JsonValue::Float(n)
+    }
+
+    fn string(&self, s: RawStr<'a>) -> JsonValue {
+        JsonValue::Str(s.decode().into_owned())
+    }
+
+    fn array(&self) -> Vec<JsonValue> {
+        Vec::new()
+    }
+
+    fn push(&self, array: &mut Vec<JsonValue>, value: JsonValue) {
+        array.push(value);
+    }
+
+    fn reserve(&self, array: &mut Vec<JsonValue>, additional: usize) {
+        array.reserve(additional);
+    }
+
+    // The reservation is a guess, so an array that ended well short of it
+    // gives the rest back.
+    fn finish_array(&self, mut array: Vec<JsonValue>) -> JsonValue {
+        if array.capacity() > 2 * array.len() {
+            array.shrink_to_fit();
+        }
+        JsonValue::Array(array)
+    }
+
+    fn object(&self) -> Map {
+        Map::new()
+    }
+
+    // Members are inserted in document order, so when a key is repeated the
+    // last occurrence wins (at the position where the key first appeared).
+    fn member(&self, object: &mut Map, key: RawStr<'a>, value: JsonValue) {
+        object.insert(key.decode().into_owned(), value);
+    }
+
+    fn finish_object(&self, object: Map) -> JsonValue {
+        JsonValue::Object(object)
+    }
+}
+
+/// Builds nothing, for when only well-formedness matters. Nothing it does
+/// allocates, so neither does parsing with it, short of spilling the stack
+/// of open containers past its inline capacity.
+pub(crate) struct Validator;
+
+impl<'a> Builder<'a> for Validator {
+    type Value = ();
+    type Array = ();
+    type Object = ();
+
+    fn null(&self) {}
+    fn bool(&self, _: bool) {}
+    fn number(&self, _: &'a str) {}
+    fn non_finite(&self, _: f64) {}
+    fn string(&self, _: RawStr<'a>) {}
+
+    fn array(&self) {}
+    fn push(&self, _: &mut (), _: ()) {}
+    fn finish_array(&self, _: ()) {}
+
+    fn object(&self) {}
+    fn member(&self, _: &mut (), _: RawStr<'a>, _: ()) {}
+    fn finish_object(&self, _: ()) {}
+}
+
+fn parse_null(s: &str) -> PResult<'_, ()> {
+    value((), tag("null")).parse(s)
+}
+
+fn parse_bool(s: &str) -> PResult<'_, bool> {
+    alt((value(true, tag("true")), value(false, tag("false")))).parse(s)
+}
+
+// number = [ '-' ] int [ frac ] [ exp ], where int is `0` or a digit sequence
+// without a leading zero. Once a '-', '.' or exponent marker has been seen,
+// digits are mandatory, so `-`, `1.` and `1e` are errors rather than a number
+// followed by garbage, and so is a zero followed by more digits, like `01`.
+fn parse_num(s: &str) -> PResult<'_, &str> {
+    let number = recognize((
+        alt((preceded(char('-'), cut(parse_int_part)), parse_int_part)),
+        opt(preceded(char('.'), cut(digit1))),
+        opt(preceded(alt((char('e'), char('E'))), cut(pair(opt(alt((char('+'), char('-')))), digit1)))),
+    ));
+    fail_as(ErrorKind::InvalidNumber, number).parse(s)
+}
+
+fn parse_int_part(s: &str) -> PResult<'_, &str> {
+    alt((terminated(tag("0"), cut(not(digit1))), digit1)).parse(s)
+}
+
+/// The numeric value of a number lexeme.
+pub(crate) enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    // Integers that fit are kept exact as `Int`; anything with a fraction or
+    // exponent, anything out of `i64` range, and `-0` (whose sign an integer
+    // can't hold) become `Float`.
+    pub(crate) fn from_lexeme(lexeme: &str) -> Number {
+        let is_integer = !lexeme.contains(['.', 'e', 'E']);
+        if is_integer && lexeme != "-0" {
+            if let Ok(i) = lexeme.parse() {
+                return Number::Int(i);
+            }
+        }
+        Number::Float(lexeme.parse().unwrap())
+    }
+}
+
+// Whether `lexeme`, a number, is an integer outside the range of `i64`.
+fn is_big_integer(lexeme: &str) -> bool {
+    !lexeme.contains(['.', 'e', 'E']) && lexeme.parse::<i64>().is_err()
+}
+
+// Whether `lexeme`, a number, is either not an integer or one within
+// ±(2^53 - 1), which JavaScript numbers hold exactly.
+fn is_safe_integer(lexeme: &str) -> bool {
+    const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+    lexeme.contains(['.', 'e', 'E'])
+        || lexeme.trim_start_matches('-').parse::<u64>().is_ok_and(|n| n <= MAX_SAFE_INTEGER)
+}
+
+enum StrFragment<'a> {
+    Literal(&'a str),
+    Escaped(char),
+}
+
+fn parse_hex4(s: &str) -> PResult<'_, u32> {
+    map_res(take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()), |hex| u32::from_str_radix(hex, 16)).parse(s)
+}
+
+// `\uXXXX`, where code points outside the BMP arrive as a UTF-16 surrogate
+// pair `\uD83D\uDE00`. A surrogate that isn't part of a well-formed pair has
+// no `char` to decode to, so it's an error.
+fn parse_unicode(s: &str) -> PResult<'_, char> {
+    let invalid = || nom::Err::Error(Error::new(s, ErrorKind::Syntax));
+    let (rest, hi) = preceded(char('u'), parse_hex4).parse(s)?;
+    match hi {
+        0xD800..=0xDBFF => {
+            let (rest, lo) = preceded(tag("\\u"), parse_hex4).parse(rest)?;
+            if !(0xDC00..=0xDFFF).contains(&lo) {
+                return Err(invalid());
+            }
+            let code = 0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00);
+            Ok((rest, char::from_u32(code).unwrap()))
+        }
+        0xDC00..=0xDFFF => Err(invalid()),
+        _ => Ok((rest, char::from_u32(hi).unwrap())),
+    }
+}
+
+// The JavaScript `\xXX` escape, for a character up to U+00FF.
+fn parse_hex2(s: &str) -> PResult<'_, char> {
+    map_res(preceded(char('x'), take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit())), |hex| {
+        u8::from_str_radix(hex, 16).map(char::from)
+    })
+    .parse(s)
+}
+
+// The character after a backslash. `\'` is only an escape inside a
+// single-quoted string, and `\xXX` and `\0` only with lenient escapes.
+fn parse_escape(s: &str, syntax: StrSyntax) -> PResult<'_, char> {
+    if syntax.quote == '\'' {
+        if let Some(rest) = s.strip_prefix('\'') {
+            return Ok((rest, '\''));
+        }
+    }
+    if syntax.lenient_escapes {
+        if let Some(rest) = s.strip_prefix('0') {
+            return Ok((rest, '\0'));
+        }
+        if let Ok(result) = parse_hex2(s) {
+            return Ok(result);
+        }
+    }
+    alt((
+        parse_unicode,
+        value('"', char('"')),
+        value('\\', char('\\')),
+        value('/', char('/')),
+        value('\u{08}', char('b')),
+        value('\u{0C}', char('f')),
+        value('\n', char('n')),
+        value('\r', char('r')),
+        value('\t', char('t')),
+    ))
+    .parse(s)
+}
+
+// A run of literal characters or a single escape in a string.
+fn parse_fragment(s: &str, syntax: StrSyntax) -> PResult<'_, StrFragment<'_>> {
+    alt((
+        // Control characters must be escaped, so a raw one ends the literal
+        // run and the string then fails to close right where it appears.
+        map(take_while1(|c: char| c != syntax.quote && c != '\\' && c >= '\u{20}'), StrFragment::Literal),
+        // A backslash commits us to an escape, so `"\q"` or a lone trailing
+        // backslash is reported as an error instead of backtracking.
+        map(
+            preceded(char('\\'), fail_as(ErrorKind::InvalidEscape, cut(|s| parse_escape(s, syntax)))),
+            StrFragment::Escaped,
+        ),
+    ))
+    .parse(s)
+}
+
+// A JavaScript-style identifier: letters, digits, `_` and `$`, not starting
+// with a digit.
+fn parse_identifier(s: &str) -> PResult<'_, &str> {
+    let is_start = |c: char| c.is_alphabetic() || c == '_' || c == '$';
+    recognize(pair(take_while_m_n(1, 1, is_start), take_while(move |c: char| is_start(c) || c.is_alphanumeric())))
+        .parse(s)
+}
+
+/// Which string literals the grammar accepts: the quote delimiting them, and
+/// whether the non-JSON escapes `lenient_escapes` enables are allowed.
+#[derive(Clone, Copy)]
+pub(crate) struct StrSyntax {
+    quote: char,
+    lenient_escapes: bool,
+}
+
+impl StrSyntax {
+    const JSON: StrSyntax = StrSyntax { quote: '"', lenient_escapes: false };
+}
+
+/// The contents of a string literal, between the quotes, exactly as they
+/// appear in the input. The escapes in it are known to be valid but haven't
+/// been decoded yet.
+#[derive(Clone, Copy)]
+pub(crate) struct RawStr<'a> {
+    body: &'a str,
+    syntax: StrSyntax,
+}
+
+impl<'a> RawStr<'a> {
+    /// Decodes the string, borrowing it from the input if it has no escapes.
+    pub(crate) fn decode(self) -> Cow<'a, str> {
+        if !self.body.contains('\\') {
+            return Cow::Borrowed(self.body);
+        }
+        let fragment = |s| parse_fragment(s, self.syntax);
+        let mut decode = fold_many0(fragment, String::new, |mut acc, fragment| {
+            match fragment {
+                StrFragment::Literal(lit) => acc.push_str(lit),
+                StrFragment::Escaped(c) => acc.push(c),
+            }
+            acc
+        });
+        let (_, decoded) = decode.parse(self.body).unwrap();
+        Cow::Owned(decoded)
+    }
+}
+
+// A string literal. This only checks the string; decoding it is left to the
+// builder, which may not need to.
+fn parse_quoted(s: &str, syntax: StrSyntax) -> PResult<'_, RawStr<'_>> {
+    let body = recognize(many0_count(|s| parse_fragment(s, syntax)));
+    map(preceded(char(syntax.quote), cut(terminated(body, char(syntax.quote)))), |body| RawStr { body, syntax })
+        .parse(s)
+}
+
+// Skips JSON's four whitespace characters. This runs before nearly every
+// token, so it's a plain loop over the bytes rather than a combinator.
+pub(crate) fn skip_whitespace(s: &str) -> &str {
+    let skipped = s.bytes().take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r')).count();
+    &s[skipped..]
+}
+
+// `// ...` up to the end of the line, or `/* ... */`.
+fn parse_comment(s: &str) -> PResult<'_, &str> {
+    alt((
+        preceded(tag("//"), take_till(|c| c == '\n')),
+        preceded(tag("/*"), cut(terminated(take_until("*/"), tag("*/")))),
+    ))
+    .parse(s)
+}
+
+/// The container half of the grammar, which needs the parse options.
+/// Values are handed to `builder` as they're recognized.
+struct JsonParser<'o, B> {
+    options: &'o ParseOptions,
+    builder: B,
+}
+
+impl<'o, B> JsonParser<'o, B> {
+    fn new(options: &'o ParseOptions, builder: B) -> Self {
+        JsonParser { options, builder }
+    }
+}
+
+// The start of a value: either all of a scalar, or a container's opening
+// bracket.
+enum Token<V> {
+    Scalar(V),
+    OpenArray,
+    OpenObject,
+}
+
+// A container whose closing bracket hasn't been reached yet. An object
+// holds the key of the member whose value is being parsed and, when
+// duplicate keys are errors, every key it has had.
+enum Open<'a, B: Builder<'a>> {
+    Array(B::Array),
+    Object(B::Object, RawStr<'a>, HashSet<Cow<'a, str>>),
+}
+
+// How many open containers the parser keeps without allocating.
+const INLINE_DEPTH: usize = 32;
+
+// How many items an array has before the parser guesses how many more it
+// will have and reserves room for them, instead of letting it double its way
+// there. Enough that the guess is a fair one and smaller arrays never pay.
+const RESERVE_AFTER: usize = 1024;
+
+// The stack of open containers. The first `INLINE_DEPTH` live inline, so
+// that parsing a document nested no deeper than that doesn't allocate for
+// them, which is what lets `validate` allocate nothing; any deeper spill
+// onto the heap. Nothing is spilled while there is room inline, so the
+// spilled items are always the innermost.
+struct Stack<T> {
+    inline: [Option<T>; INLINE_DEPTH],
+    inline_len: usize,
+    spilled: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Self {
+        Stack { inline: [const { None }; INLINE_DEPTH], inline_len: 0, spilled: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.inline_len + self.spilled.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inline_len == 0
+    }
+
+    fn push(&mut self, item: T) {
+        if self.inline_len < INLINE_DEPTH {
+            self.inline[self.inline_len] = Some(item);
+            self.inline_len += 1;
+        } else {
+            self.spilled.push(item);
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.inline_len == INLINE_DEPTH && !self.spilled.is_empty() {
+            return self.spilled.pop();
+        }
+        self.inline_len = self.inline_len.checked_sub(1)?;
+        self.inline[self.inline_len].take()
+    }
+
+    fn last_mut(&mut self) -> Option<&mut T> {
+        if self.inline_len == INLINE_DEPTH && !self.spilled.is_empty() {
+            return self.spilled.last_mut();
+        }
+        self.inline.get_mut(self.inline_len.checked_sub(1)?)?.as_mut()
+    }
+}
+
+impl<'a, B: Builder<'a>> Open<'a, B> {
+    fn close(&self) -> char {
+        match self {
+            Open::Array(_) => ']',
+            Open::Object(..) => '}',
+        }
+    }
+}
+
+// Errors inside a container are committed, as if by `cut`.
+fn commit(e: nom::Err<Error<'_>>, nested: bool) -> nom::Err<Error<'_>> {
+    match e {
+        nom::Err::Error(e) if nested => nom::Err::Failure(e),
+        e => e,
+    }
+}
+
+impl<'a, B: Builder<'a>> JsonParser<'_, B> {
+    // Skips insignificant whitespace, and comments too when they're enabled.
+    fn ws(&self, s: &'a str) -> PResult<'a, ()> {
+        let mut rest = skip_whitespace(s);
+        if self.options.allow_comments {
+            while rest.starts_with('/') {
+                match parse_comment(rest) {
+                    Ok((after, _)) => rest = skip_whitespace(after),
+                    Err(nom::Err::Error(_)) => break,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        Ok((rest, ()))
+    }
+
+    // A comma between elements, at the start of `s` with the whitespace
+    // before it already skipped. When trailing commas are allowed, a comma
+    // that is followed by the closing bracket is left for `close` instead.
+    fn separator(&self, s: &'a str, close: char) -> PResult<'a, char> {
+        if self.options.allow_trailing_commas {
+            terminated(char(','), not(preceded(|s| self.ws(s), char(close)))).parse(s)
+        } else {
+            char(',').parse(s)
+        }
+    }
+
+    // The closing bracket, likewise with the whitespace before it skipped.
+    fn close(&self, s: &'a str, close: char) -> PResult<'a, char> {
+        if self.options.allow_trailing_commas {
+            preceded(opt(terminated(char(','), |s| self.ws(s))), char(close)).parse(s)
+        } else {
+            char(close).parse(s)
+        }
+    }
+
+    // Containers are tracked on an explicit stack rather than by recursion,
+    // so how deeply a document may nest is limited by `max_depth` alone and
+    // not by the size of the call stack. Once a container's opening bracket
+    // has been consumed the rest of it is committed, so errors are reported
+    // where they happen instead of backtracking to its start.
+    fn parse_value(&self, s: &'a str) -> PResult<'a, B::Value> {
+        // Each open container, with the input from its opening bracket and
+        // how many items it has had.
+        let mut stack: Stack<(&'a str, usize, Open<'a, B>)> = Stack::new();
+        let mut rest = s;
+        'value: loop {
+            let nested = !stack.is_empty();
+            let (start, _) = self.ws(rest)?;
+            if !nested && self.options.require_container_root {
+                peek(one_of("[{")).parse(start)?;
+            }
+            let (after, token) = self.token(start).map_err(|e| commit(e, nested))?;
+            rest = after;
+            let mut value = match token {
+                Token::Scalar(value) => self.builder.span(value, start, rest),
+                Token::OpenArray | Token::OpenObject => {
+                    if stack.len() >= self.options.max_depth {
+                        return Err(nom::Err::Failure(Error::new(start, ErrorKind::DepthLimitExceeded)));
+                    }
+                    let mut open = match token {
+                        Token::OpenArray => Open::Array(self.builder.array()),
+                        // The key is filled in once it has been parsed.
+                        _ => {
+                            let key = RawStr { body: "", syntax: StrSyntax::JSON };
+                            Open::Object(self.builder.object(), key, HashSet::new())
+                        }
+                    };
+                    // The whitespace inside the bracket is skipped once, for
+                    // both the empty case and the first item.
+                    (rest, _) = self.ws(rest)?;
+                    match rest.strip_prefix(open.close()) {
+                        Some(after) => {
+                            rest = after;
+                            self.builder.span(self.finish(open), start, rest)
+                        }
+                        None => {
+                            (rest, _) = self.next_item(rest, 0, &mut open)?;
+                            stack.push((start, 1, open));
+                            continue 'value;
+                        }
+                    }
+                }
+            };
+
+            // A complete value goes into the innermost open container, which
+            // then either expects another item or closes, completing a value
+            // of its own.
+            while let Some((start, items, open)) = stack.last_mut() {
+                match open {
+                    Open::Array(array) => self.builder.push(array, value),
+                    Open::Object(object, key, _) => self.builder.member(object, *key, value),
+                }
+                let close = open.close();
+                // Likewise the whitespace before a separator or the closing
+                // bracket.
+                (rest, _) = self.ws(rest)?;
+                match self.separator(rest, close) {
+                    Ok((after, _)) => {
+                        (rest, _) = self.next_item(after, *items, open)?;
+                        *items += 1;
+                        if *items == RESERVE_AFTER {
+                            if let Open::Array(array) = open {
+                                self.builder.reserve(array, self.estimate_items(start, rest));
+                            }
+                        }
+                        continue 'value;
+                    }
+                    Err(nom::Err::Error(_)) => {}
+                    Err(e) => return Err(e),
+                }
+                (rest, _) = cut(|s| self.close(s, close)).parse(rest)?;
+                let (start, _, open) = stack.pop().unwrap();
+                value = self.builder.span(self.finish(open), start, rest);
+            }
+            return Ok((rest, value));
+        }
+    }
+
+    fn token(&self, s: &'a str) -> PResult<'a, Token<B::Value>> {
+        alt((
+            |s| {
+                let (rest, raw) = self.parse_string(s)?;
+                self.check_length(s, raw)?;
+                Ok((rest, Token::Scalar(self.builder.string(raw))))
+            },
+            // Has to come first: a '-' commits `parse_num` to a number.
+            map(|s| self.parse_non_finite(s), Token::Scalar),
+            |s| {
+                let (rest, lexeme) = parse_num(s)?;
+                if self.options.enforce_js_safe_integers && !is_safe_integer(lexeme) {
+                    return Err(nom::Err::Failure(Error::new(s, ErrorKind::UnsafeInteger)));
+                }
+                Ok((rest, Token::Scalar(self.number(lexeme))))
+            },
+            map(parse_bool, |b| Token::Scalar(self.builder.bool(b))),
+            map(parse_null, |_| Token::Scalar(self.builder.null())),
+            map(char('['), |_| Token::OpenArray),
+            map(char('{'), |_| Token::OpenObject),
+        ))
+        .parse(s)
+    }
+
+    // Whatever comes before the next item of `open`, which has had `items`
+    // so far: nothing in an array, and a key and a colon in an object.
+    fn next_item(&self, s: &'a str, items: usize, open: &mut Open<'a, B>) -> PResult<'a, ()> {
+        let (start, _) = self.ws(s)?;
+        let (limit, max) = match open {
+            Open::Array(_) => (Limit::ArrayLength, self.options.max_array_length),
+            Open::Object(..) => (Limit::ObjectEntries, self.options.max_object_entries),
+        };
+        if items >= max {
+            return Err(nom::Err::Failure(Error::new(start, ErrorKind::LimitExceeded(limit))));
+        }
+        let Open::Object(_, key, seen) = open else {
+            return Ok((start, ()));
+        };
+        let rest;
+        (rest, *key) = cut(|s| self.parse_key(s)).parse(start)?;
+        self.check_length(start, *key)?;
+        if self.options.error_on_duplicate_keys && !seen.insert(key.decode()) {
+            return Err(nom::Err::Failure(Error::new(start, ErrorKind::DuplicateKey)));
+        }
+        self.builder.key(*key);
+        value((), cut(preceded(|s| self.ws(s), char(':')))).parse(rest)
+    }
+
+    // Fails at `start`, where the string `raw` was parsed from, if it's
+    // longer than the limit.
+    fn check_length(&self, start: &'a str, raw: RawStr<'a>) -> Result<(), nom::Err<Error<'a>>> {
+        if raw.body.len() > self.options.max_string_length {
+            return Err(nom::Err::Failure(Error::new(start, ErrorKind::LimitExceeded(Limit::StringLength))));
+        }
+        Ok(())
+    }
+
+    // How many more items an array that opened at `start` might have, with
+    // `rest` left, if they're as long as the `RESERVE_AFTER` items so far and
+    // the array runs to the end of the input, which is true of a root array.
+    // Each item takes at least two bytes with its comma, which bounds it.
+    fn estimate_items(&self, start: &'a str, rest: &'a str) -> usize {
+        let taken = start.len() - rest.len();
+        let limit = self.options.max_array_length.saturating_sub(RESERVE_AFTER);
+        (rest.len() / taken.div_ceil(RESERVE_AFTER)).min(rest.len() / 2 + 1).min(limit)
+    }
+
+    fn finish(&self, open: Open<'a, B>) -> B::Value {
+        match open {
+            Open::Array(array) => self.builder.finish_array(array),
+            Open::Object(object, ..) => self.builder.finish_object(object),
+        }
+    }
+
+    fn number(&self, lexeme: &'a str) -> B::Value {
+        if let Some(handler) = &self.options.number_handler {
+            self.builder.custom_number(lexeme, handler.as_ref())
+        } else if self.options.preserve_number_strings {
+            self.builder.raw_number(lexeme)
+        } else if self.options.big_integers && is_big_integer(lexeme) {
+            self.builder.big_integer(lexeme)
+        } else {
+            self.builder.number(lexeme)
+        }
+    }
+
+    // The bare `NaN`, `Infinity` and `-Infinity` tokens some JavaScript
+    // serializers emit, when they're enabled.
+    fn parse_non_finite(&self, s: &'a str) -> PResult<'a, B::Value> {
+        if !self.options.allow_non_finite {
+            return Err(nom::Err::Error(Error::new(s, ErrorKind::Syntax)));
+        }
+        let token = alt((
+            value(f64::NAN, tag("NaN")),
+            value(f64::INFINITY, tag("Infinity")),
+            value(f64::NEG_INFINITY, tag("-Infinity")),
+        ));
+        map(token, |n| self.builder.non_finite(n)).parse(s)
+    }
+
+    fn parse_string(&self, s: &'a str) -> PResult<'a, RawStr<'a>> {
+        let double = StrSyntax { quote: '"', lenient_escapes: self.options.lenient_escapes };
+        if self.options.allow_single_quotes {
+            let single = StrSyntax { quote: '\'', ..double };
+            alt((|s| parse_quoted(s, double), |s| parse_quoted(s, single))).parse(s)
+        } else {
+            parse_quoted(s, double)
+        }
+    }
+
+    // An object key, which has to be a string unless unquoted keys are
+    // allowed. Otherwise a bare identifier is a common mistake (and valid
+    // JavaScript), so it gets an error of its own.
+    fn parse_key(&self, s: &'a str) -> PResult<'a, RawStr<'a>> {
+        if self.options.allow_unquoted_keys {
+            // An identifier has no escapes, so it can stand in for the body
+            // of a string literal as it is.
+            let identifier = map(parse_identifier, |body| RawStr { body, syntax: StrSyntax::JSON });
+            return alt((|s| self.parse_string(s), identifier)).parse(s);
+        }
+        match self.parse_string(s) {
+            Err(nom::Err::Error(e)) if parse_identifier(s).is_ok() => {
+                Err(nom::Err::Error(Error::new(e.input, ErrorKind::UnquotedKey)))
+            }
+            result => result,
+        }
+    }
+
+    // Parses one value from the start of `start`, a suffix of `input`, and
+    // returns it along with whatever follows it, untouched. Errors are
+    // positioned within `input`.
+    fn prefix(&self, input: &'a str, start: &'a str) -> Result<(B::Value, &'a str), ParseError> {
+        if let Some(limit) = self.options.max_input_bytes.filter(|&limit| input.len() > limit) {
+            let mut offset = limit;
+            while !input.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            return Err(ParseError::InputTooLarge { limit, position: Position::at(input, offset) });
+        }
+        match self.parse_value(start) {
+            Ok((rest, value)) => Ok((value, rest)),
+            Err(e) => Err(self.error(input, e)),
+        }
+    }
+
+    // Like `prefix`, but the value must be all that's left of `input`.
+    fn document(&self, input: &'a str, start: &'a str) -> Result<B::Value, ParseError> {
+        let (value, rest) = self.prefix(input, start)?;
+        match self.ws(rest) {
+            Ok(("", _)) => Ok(value),
+            Ok((rest, _)) => Err(ParseError::TrailingData { position: Position::of_remaining(input, rest) }),
+            Err(e) => Err(self.error(input, e)),
+        }
+    }
+
+    fn error(&self, input: &str, error: nom::Err<Error<'a>>) -> ParseError {
+        let error = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => Error::new("", ErrorKind::Syntax),
+        };
+        let position = Position::of_remaining(input, error.input);
+        match error.kind {
+            ErrorKind::Syntax => match error.input.chars().next() {
+                Some(found) => ParseError::UnexpectedChar { found, position },
+                None => ParseError::UnexpectedEof { position },
+            },
+            ErrorKind::InvalidNumber => ParseError::InvalidNumber { position },
+            ErrorKind::InvalidEscape => ParseError::InvalidEscape { position },
+            ErrorKind::UnquotedKey => ParseError::UnquotedKey { position },
+            ErrorKind::DepthLimitExceeded => ParseError::DepthLimitExceeded { limit: self.options.max_depth, position },
+            ErrorKind::LimitExceeded(what) => {
+                let limit = match what {
+                    Limit::StringLength => self.options.max_string_length,
+                    Limit::ArrayLength => self.options.max_array_length,
+                    Limit::ObjectEntries => self.options.max_object_entries,
+                };
+                ParseError::LimitExceeded { what, limit, position }
+            }
+            ErrorKind::UnsafeInteger => ParseError::UnsafeInteger { position },
+            ErrorKind::DuplicateKey => {
+                // The error points at the key, which has already parsed once.
+                let key = self.parse_key(error.input).map(|(_, key)| key.decode().into_owned());
+                ParseError::DuplicateKey { key: key.unwrap_or_default(), position }
+            }
+        }
+    }
+}
+
+/// Skips the byte order mark some editors start UTF-8 files with. It's only
+/// meaningful as the very first character of the input, so this is for
+/// where a document starts: a BOM anywhere else is still a syntax error.
+pub(crate) fn skip_bom(input: &str) -> &str {
+    input.strip_prefix('\u{FEFF}').unwrap_or(input)
+}
+
+/// Parses `input` as a single JSON document, reporting trailing data and
+/// grammar failures as [`ParseError`]s positioned within `input`.
+pub(crate) fn parse_document<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: B,
+) -> Result<B::Value, ParseError> {
+    parse_document_at(input, skip_bom(input), options, builder)
+}
+
+/// Parses what's left of `input` from `start`, a suffix of it, as a single
+/// JSON document like `parse_document`, but without skipping a BOM.
+pub(crate) fn parse_document_at<'a, B: Builder<'a>>(
+    input: &'a str,
+    start: &'a str,
+    options: &ParseOptions,
+    builder: B,
+) -> Result<B::Value, ParseError> {
+    JsonParser::new(options, builder).document(input, start)
+}
+
+/// Parses one value from `start`, a suffix of `input`, returning it and the
+/// rest of the input. Errors are positioned within `input`.
+pub(crate) fn parse_prefix<'a, B: Builder<'a>>(
+    input: &'a str,
+    start: &'a str,
+    options: &ParseOptions,
+    builder: B,
+) -> Result<(B::Value, &'a str), ParseError> {
+    JsonParser::new(options, builder).prefix(input, start)
+}
+
+/// A token of the grammar on its own, for the lexer that
+/// [`tokenize`](crate::tokenize) exposes.
+pub(crate) enum Lexeme<'a> {
+    Punct(char),
+    Str(RawStr<'a>),
+    Number(&'a str),
+    Bool(bool),
+    Null,
+}
+
+/// Reads the token at the very start of `s`, which must already be past
+/// any whitespace, and describes a failure within `input` as `parse` would.
+pub(crate) fn lex<'a>(input: &'a str, s: &'a str) -> Result<(&'a str, Lexeme<'a>), ParseError> {
+    let lexeme = alt((
+        map(one_of("{}[]:,"), Lexeme::Punct),
+        map(|s| parse_quoted(s, StrSyntax::JSON), Lexeme::Str),
+        map(parse_num, Lexeme::Number),
+        map(parse_bool, Lexeme::Bool),
+        map(parse_null, |_| Lexeme::Null),
+    ))
+    .parse(s);
+    lexeme.map_err(|e| JsonParser::new(&ParseOptions::default(), Validator).error(input, e))
+}
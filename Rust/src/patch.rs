@@ -0,0 +1,127 @@
+//! [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch support.
+
+use std::fmt;
+
+use crate::pointer::{array_index, resolve_mut, tokens};
+use crate::JsonValue;
+
+/// The error returned when a JSON Patch cannot be applied.
+///
+/// Operations are numbered from 0 in the order they appear in the patch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// The patch is not an array of operations.
+    NotAnArray,
+    /// The operation at `index` is not an object, has an unknown `op`, or is
+    /// missing a member its `op` needs.
+    InvalidOperation { index: usize },
+    /// The `path` (or `from`) of the operation at `index` is malformed or
+    /// names a location the operation can't act on, such as a missing member
+    /// or an array index out of bounds.
+    InvalidPath { index: usize, path: String },
+    /// The `test` operation at `index` found a different value at `path`.
+    TestFailed { index: usize, path: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::NotAnArray => f.write_str("a JSON Patch must be an array of operations"),
+            PatchError::InvalidOperation { index } => write!(f, "operation {} is malformed", index),
+            PatchError::InvalidPath { index, path } => {
+                write!(f, "operation {} cannot act on path {:?}", index, path)
+            }
+            PatchError::TestFailed { index, path } => write!(f, "test operation {} failed at {:?}", index, path),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl JsonValue {
+    /// Applies an [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON
+    /// Patch to this value.
+    ///
+    /// `patch` is an array of operations such as
+    /// `{"op": "add", "path": "/a/b", "value": 1}`, addressed with JSON
+    /// Pointers and applied in order. All six operations are supported:
+    /// `add`, `remove`, `replace`, `move`, `copy` and `test`. A `test`
+    /// compares numbers by value, so `1` matches `1.0`.
+    ///
+    /// The patch is all or nothing: if any operation fails, including a
+    /// `test`, the error is returned and this value is left as it was.
+    pub fn apply_patch(&mut self, patch: &JsonValue) -> Result<(), PatchError> {
+        let JsonValue::Array(operations) = patch else {
+            return Err(PatchError::NotAnArray);
+        };
+        let mut patched = self.clone();
+        for (index, operation) in operations.iter().enumerate() {
+            apply(&mut patched, operation, index)?;
+        }
+        *self = patched;
+        Ok(())
+    }
+}
+
+fn apply(doc: &mut JsonValue, operation: &JsonValue, index: usize) -> Result<(), PatchError> {
+    let invalid = || PatchError::InvalidOperation { index };
+    let member = |name| operation.get(name).ok_or_else(invalid);
+    let pointer = |name| member(name)?.as_str().ok_or_else(invalid);
+    let path = pointer("path")?;
+    let bad_path = |path: &str| PatchError::InvalidPath { index, path: path.to_string() };
+
+    match member("op")?.as_str().ok_or_else(invalid)? {
+        "add" => add(doc, path, member("value")?.clone()).ok_or_else(|| bad_path(path)),
+        "remove" => doc.remove_pointer(path).map(drop).ok_or_else(|| bad_path(path)),
+        "replace" => {
+            let value = member("value")?.clone();
+            *doc.pointer_mut(path).ok_or_else(|| bad_path(path))? = value;
+            Ok(())
+        }
+        "move" => {
+            let from = pointer("from")?;
+            // A value can't be moved into one of its own descendants.
+            if path.strip_prefix(from).is_some_and(|rest| rest.starts_with('/')) {
+                return Err(bad_path(path));
+            }
+            let value = doc.remove_pointer(from).ok_or_else(|| bad_path(from))?;
+            add(doc, path, value).ok_or_else(|| bad_path(path))
+        }
+        "copy" => {
+            let from = pointer("from")?;
+            let value = doc.pointer(from).ok_or_else(|| bad_path(from))?.clone();
+            add(doc, path, value).ok_or_else(|| bad_path(path))
+        }
+        "test" => {
+            let expected = member("value")?;
+            match doc.pointer(path) {
+                Some(actual) if actual.loosely_eq(expected) => Ok(()),
+                Some(_) => Err(PatchError::TestFailed { index, path: path.to_string() }),
+                None => Err(bad_path(path)),
+            }
+        }
+        _ => Err(invalid()),
+    }
+}
+
+// Adds `value` at `path`: replacing the document for the empty path, setting
+// an object member, or inserting into an array, where `-` appends.
+fn add(doc: &mut JsonValue, path: &str, value: JsonValue) -> Option<()> {
+    let mut tokens = tokens(path)?;
+    let Some(last) = tokens.pop() else {
+        *doc = value;
+        return Some(());
+    };
+    match resolve_mut(doc, &tokens)? {
+        JsonValue::Object(members) => {
+            members.insert(last.into_owned(), value);
+        }
+        JsonValue::Array(items) if last == "-" => items.push(value),
+        JsonValue::Array(items) => {
+            let i = array_index(&last).filter(|&i| i <= items.len())?;
+            items.insert(i, value);
+        }
+        _ => return None,
+    }
+    Some(())
+}
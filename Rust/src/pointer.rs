@@ -0,0 +1,197 @@
+//! [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer support.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{JsonValue, Map};
+
+/// The error returned when a value can't be set at a JSON Pointer.
+///
+/// `path` is the part of the pointer up to and including the token that
+/// couldn't be followed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointerError {
+    /// The pointer is not empty and doesn't start with `/`.
+    Malformed,
+    /// `path` names a missing member, or an array element past the end.
+    NotFound { path: String },
+    /// `path` steps into a value that isn't an object or array, or into an
+    /// array with a token that isn't an index.
+    TypeMismatch { path: String },
+}
+
+impl fmt::Display for PointerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointerError::Malformed => f.write_str("a JSON Pointer must be empty or start with `/`"),
+            PointerError::NotFound { path } => write!(f, "nothing found at {:?}", path),
+            PointerError::TypeMismatch { path } => write!(f, "cannot step into the value at {:?}", path),
+        }
+    }
+}
+
+impl std::error::Error for PointerError {}
+
+/// Splits a pointer into its unescaped reference tokens, or `None` if it is
+/// not a valid pointer (anything non-empty must start with `/`).
+pub(crate) fn tokens(ptr: &str) -> Option<Vec<Cow<'_, str>>> {
+    if ptr.is_empty() {
+        return Some(Vec::new());
+    }
+    let rest = ptr.strip_prefix('/')?;
+    Some(rest.split('/').map(unescape).collect())
+}
+
+// `~1` decodes to `/` and `~0` to `~`, in that order, so `~01` is `~1`.
+fn unescape(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Escapes `key` for use as a reference token, the reverse of `unescape`.
+pub(crate) fn escape(key: &str) -> Cow<'_, str> {
+    if key.contains(['~', '/']) {
+        Cow::Owned(key.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(key)
+    }
+}
+
+/// Parses an array index token: a non-negative decimal with no leading zeros.
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    let leading_zero = token.len() > 1 && token.starts_with('0');
+    if token.is_empty() || leading_zero || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Follows already split `tokens` down from `value`.
+pub(crate) fn resolve_mut<'v>(value: &'v mut JsonValue, tokens: &[Cow<'_, str>]) -> Option<&'v mut JsonValue> {
+    tokens.iter().try_fold(value, |target, token| match target {
+        JsonValue::Object(members) => members.get_mut(token),
+        JsonValue::Array(items) => items.get_mut(array_index(token)?),
+        _ => None,
+    })
+}
+
+impl JsonValue {
+    /// Looks up a value by JSON Pointer, such as `/address/city` or
+    /// `/scores/0`.
+    ///
+    /// The empty pointer refers to the whole document. Within a token, `~1`
+    /// stands for `/` and `~0` for `~`. Returns `None` if the pointer is
+    /// malformed, names a missing member or element, or steps into a value
+    /// that isn't an object or array.
+    pub fn pointer(&self, ptr: &str) -> Option<&JsonValue> {
+        tokens(ptr)?.iter().try_fold(self, |target, token| match target {
+            JsonValue::Object(members) => members.get(token),
+            JsonValue::Array(items) => items.get(array_index(token)?),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if the pointer is valid and names a value in this
+    /// document, as [`pointer`](JsonValue::pointer) would find.
+    pub fn contains_pointer(&self, ptr: &str) -> bool {
+        self.pointer(ptr).is_some()
+    }
+
+    /// Like [`pointer`](JsonValue::pointer), but returns a mutable reference.
+    pub fn pointer_mut(&mut self, ptr: &str) -> Option<&mut JsonValue> {
+        resolve_mut(self, &tokens(ptr)?)
+    }
+
+    /// Removes the value at `ptr` and returns it, or returns `None` if there
+    /// is nothing there. Later array elements and object members shift
+    /// down to close the gap. The empty pointer can't be removed, since the
+    /// whole document would have nothing to leave behind.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let mut doc = json!({"scores": [90, 85, 95]});
+    /// assert_eq!(doc.remove_pointer("/scores/0"), Some(json!(90)));
+    /// assert_eq!(doc, json!({"scores": [85, 95]}));
+    /// ```
+    pub fn remove_pointer(&mut self, ptr: &str) -> Option<JsonValue> {
+        let mut tokens = tokens(ptr)?;
+        let last = tokens.pop()?;
+        match resolve_mut(self, &tokens)? {
+            JsonValue::Object(members) => members.remove(&last),
+            JsonValue::Array(items) => {
+                let i = array_index(&last).filter(|&i| i < items.len())?;
+                Some(items.remove(i))
+            }
+            _ => None,
+        }
+    }
+
+    /// Stores `value` at the location `ptr` names, replacing what was there.
+    ///
+    /// Everything along the way has to exist already. The last token may
+    /// name a new member of an object, or be `-` to append to an array; the
+    /// empty pointer replaces the whole document.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let mut doc = json!({"address": {"city": "北京"}, "tags": []});
+    /// doc.set_pointer("/address/city", json!("上海")).unwrap();
+    /// doc.set_pointer("/tags/-", json!("new")).unwrap();
+    /// assert_eq!(doc, json!({"address": {"city": "上海"}, "tags": ["new"]}));
+    /// ```
+    pub fn set_pointer(&mut self, ptr: &str, value: JsonValue) -> Result<(), PointerError> {
+        set(self, ptr, value, false)
+    }
+
+    /// Like [`set_pointer`](JsonValue::set_pointer), but creates any members
+    /// missing along the way as empty objects.
+    pub fn set_pointer_creating(&mut self, ptr: &str, value: JsonValue) -> Result<(), PointerError> {
+        set(self, ptr, value, true)
+    }
+}
+
+fn set(doc: &mut JsonValue, ptr: &str, value: JsonValue, create: bool) -> Result<(), PointerError> {
+    let mut tokens = tokens(ptr).ok_or(PointerError::Malformed)?;
+    let Some(last) = tokens.pop() else {
+        *doc = value;
+        return Ok(());
+    };
+    // The pointer as far as the token at `i`, for errors.
+    let path = |i: usize| tokens.iter().chain([&last]).take(i + 1).map(|token| format!("/{}", escape(token))).collect();
+
+    let mut target = doc;
+    for (i, token) in tokens.iter().enumerate() {
+        target = match target {
+            JsonValue::Object(members) => {
+                if create && !members.contains_key(token) {
+                    members.insert(token.to_string(), JsonValue::Object(Map::new()));
+                }
+                members.get_mut(token).ok_or_else(|| PointerError::NotFound { path: path(i) })?
+            }
+            JsonValue::Array(items) => {
+                let index = array_index(token).ok_or_else(|| PointerError::TypeMismatch { path: path(i) })?;
+                items.get_mut(index).ok_or_else(|| PointerError::NotFound { path: path(i) })?
+            }
+            _ => return Err(PointerError::TypeMismatch { path: path(i) }),
+        };
+    }
+
+    let i = tokens.len();
+    match target {
+        JsonValue::Object(members) => {
+            members.insert(last.into_owned(), value);
+        }
+        JsonValue::Array(items) if last == "-" => items.push(value),
+        JsonValue::Array(items) => {
+            let index = array_index(&last).ok_or_else(|| PointerError::TypeMismatch { path: path(i) })?;
+            *items.get_mut(index).ok_or_else(|| PointerError::NotFound { path: path(i) })? = value;
+        }
+        _ => return Err(PointerError::TypeMismatch { path: path(i) }),
+    }
+    Ok(())
+}
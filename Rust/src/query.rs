@@ -0,0 +1,126 @@
+//! A minimal [JSONPath](https://www.rfc-editor.org/rfc/rfc9535) evaluator.
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while1},
+    character::complete::{char, digit1},
+    combinator::{all_consuming, map, map_res, value},
+    multi::many0,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+
+use crate::JsonValue;
+
+#[derive(Clone)]
+enum Selector {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+// A step in a path. A recursive step applies its selector to the current
+// values and all of their descendants instead of just the current values.
+struct Segment {
+    recursive: bool,
+    selector: Selector,
+}
+
+fn parse_dotted(s: &str) -> IResult<&str, Selector> {
+    let name = take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '-');
+    alt((value(Selector::Wildcard, char('*')), map(name, |key: &str| Selector::Key(key.to_string())))).parse(s)
+}
+
+fn parse_bracketed(s: &str) -> IResult<&str, Selector> {
+    let quoted = delimited(char('\''), take_till(|c| c == '\''), char('\''));
+    delimited(
+        char('['),
+        alt((
+            value(Selector::Wildcard, char('*')),
+            map(map_res(digit1, str::parse), Selector::Index),
+            map(quoted, |key: &str| Selector::Key(key.to_string())),
+        )),
+        char(']'),
+    )
+    .parse(s)
+}
+
+fn parse_segment(s: &str) -> IResult<&str, Segment> {
+    alt((
+        map(preceded(tag(".."), alt((parse_dotted, parse_bracketed))), |selector| Segment {
+            recursive: true,
+            selector,
+        }),
+        map(alt((preceded(char('.'), parse_dotted), parse_bracketed)), |selector| Segment {
+            recursive: false,
+            selector,
+        }),
+    ))
+    .parse(s)
+}
+
+fn parse_path(path: &str) -> Option<Vec<Segment>> {
+    let (_, segments) = all_consuming(preceded(char('$'), many0(parse_segment))).parse(path).ok()?;
+    Some(segments)
+}
+
+// Pushes `value` and everything nested inside it, parents first.
+fn push_descendants<'v>(value: &'v JsonValue, out: &mut Vec<&'v JsonValue>) {
+    out.push(value);
+    for child in value.entries().map(|(_, child)| child).chain(value.elements()) {
+        push_descendants(child, out);
+    }
+}
+
+fn select<'v>(value: &'v JsonValue, selector: &Selector, out: &mut Vec<&'v JsonValue>) {
+    match (selector, value) {
+        (Selector::Key(key), JsonValue::Object(members)) => out.extend(members.get(key)),
+        (Selector::Index(i), JsonValue::Array(items)) => out.extend(items.get(*i)),
+        (Selector::Wildcard, JsonValue::Object(members)) => out.extend(members.values()),
+        (Selector::Wildcard, JsonValue::Array(items)) => out.extend(items),
+        _ => {}
+    }
+}
+
+impl JsonValue {
+    /// Finds the values matching a JSONPath expression.
+    ///
+    /// Supported: the root `$`, child members as `.key` or `['key']`, array
+    /// elements as `[0]`, wildcards as `.*` or `[*]`, and recursive descent
+    /// as `..key` (or `..*`, `..[0]`). Selectors that don't apply, like an
+    /// index into an object, match nothing. So does a path this subset can't
+    /// parse.
+    ///
+    /// Matches come in document order, except that recursive descent visits
+    /// each value before the values inside it, so `$..id` lists the root's
+    /// `id` ahead of any nested one.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let doc = json!({"scores": [90, 85], "address": {"code": [200]}});
+    /// assert_eq!(doc.query("$.scores[*]"), [&json!(90), &json!(85)]);
+    /// assert_eq!(doc.query("$..code[0]"), [&json!(200)]);
+    /// ```
+    pub fn query(&self, path: &str) -> Vec<&JsonValue> {
+        let Some(segments) = parse_path(path) else {
+            return Vec::new();
+        };
+        let mut current = vec![self];
+        for segment in &segments {
+            if segment.recursive {
+                let mut all = Vec::new();
+                for value in current {
+                    push_descendants(value, &mut all);
+                }
+                current = all;
+            }
+            let mut next = Vec::new();
+            for value in current {
+                select(value, &segment.selector, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+}
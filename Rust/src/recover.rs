@@ -0,0 +1,107 @@
+use crate::{JsonValue, ParseError, ParseOptions, Position};
+
+// Gives up on input that needs more repairs than this, which also bounds the
+// work done on pathological input.
+const MAX_REPAIRS: usize = 100;
+
+// A fix for the error at `offset` in the text being parsed.
+enum Repair {
+    Insert(usize, char),
+    Remove(usize),
+}
+
+/// Guesses a fix for `error`, found at byte `offset` of `text`: a comma
+/// where one is missing between two values, or the removal of a comma just
+/// before a closing bracket.
+fn repair(text: &str, offset: usize, error: &ParseError) -> Option<Repair> {
+    let ParseError::UnexpectedChar { found, .. } = error else {
+        return None;
+    };
+    let before = text[..offset].trim_end();
+    let previous = before.chars().next_back()?;
+    match (previous, found) {
+        (',', ']' | '}') => Some(Repair::Remove(before.len() - 1)),
+        ('"' | ']' | '}' | '0'..='9' | 'e' | 'l', '"' | '[' | '{' | '-' | '0'..='9' | 't' | 'f' | 'n') => {
+            Some(Repair::Insert(offset, ','))
+        }
+        _ => None,
+    }
+}
+
+/// Parses `input`, repairing missing and trailing commas as it goes.
+pub(crate) fn parse_recovering(input: &str) -> (Option<JsonValue>, Vec<ParseError>) {
+    let mut text = input.to_string();
+    // The offset in `input` that each byte offset in `text` came from.
+    let mut origins: Vec<usize> = (0..=input.len()).collect();
+    let mut errors = Vec::new();
+    loop {
+        let mut error = match crate::parse(&text) {
+            Ok(value) => return (Some(value), errors),
+            Err(error) => error,
+        };
+        let offset = error.position().map_or(text.len(), |position| position.offset);
+        let fix = repair(&text, offset, &error).filter(|_| errors.len() < MAX_REPAIRS);
+        if let Some(position) = error.position_mut() {
+            *position = Position::at(input, origins[offset]);
+        }
+        errors.push(error);
+        match fix {
+            Some(Repair::Insert(at, c)) => {
+                text.insert(at, c);
+                origins.insert(at, origins[at]);
+            }
+            Some(Repair::Remove(at)) => {
+                text.remove(at);
+                origins.remove(at);
+            }
+            None => return (None, errors),
+        }
+    }
+}
+
+/// Parses `input` with trailing commas and unquoted keys allowed, closing
+/// any arrays and objects still open when the input ends.
+pub(crate) fn parse_repairing(input: &str) -> Result<JsonValue, ParseError> {
+    let options = ParseOptions { allow_trailing_commas: true, allow_unquoted_keys: true, ..ParseOptions::default() };
+    let error = match crate::parse_with_options(input, &options) {
+        Err(error @ ParseError::UnexpectedEof { .. }) => error,
+        result => return result,
+    };
+    // If closing the brackets doesn't help, the input was cut off somewhere
+    // they can't fix, such as after a key, and the end is still the problem.
+    match unclosed(input) {
+        Some(closers) if !closers.is_empty() => {
+            crate::parse_with_options(&format!("{}{}", input, closers), &options).map_err(|_| error)
+        }
+        _ => Err(error),
+    }
+}
+
+/// The brackets that would close every array and object left open at the
+/// end of `input`, innermost first, or `None` if it ends inside a string.
+fn unclosed(input: &str) -> Option<String> {
+    let mut open = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in input.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => open.push(']'),
+            '{' => open.push('}'),
+            ']' | '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    (!in_string).then(|| open.iter().rev().collect())
+}
@@ -0,0 +1,371 @@
+use std::fmt::{self, Write};
+use std::io;
+
+use crate::value::integer_digits;
+use crate::{JsonValue, LineEnding, Map, SerializeOptions};
+
+// What `Display` and the other fixed formats use.
+const PLAIN: SerializeOptions = SerializeOptions {
+    escape_forward_slashes: false,
+    ascii_only: false,
+    sort_keys: false,
+    float_precision: None,
+    line_ending: LineEnding::Lf,
+};
+
+/// Formats the value as compact JSON, with no whitespace between tokens.
+///
+/// Floats are written with the fewest digits that parse back to exactly the
+/// same value, keeping a `.0` on whole numbers so they stay floats. Non-finite
+/// numbers have no JSON representation and are written as `null`. Strings
+/// escape `"`, `\`, the control characters U+0000 to U+001F and DEL
+/// (U+007F), using the short forms like `\n` where JSON has them and
+/// `\u00XX` otherwise; every other character is written as it is.
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_compact(f, self, &PLAIN)
+    }
+}
+
+impl JsonValue {
+    /// Formats the value as compact JSON like `to_string`, adjusted by
+    /// `options`.
+    ///
+    /// ```
+    /// use json_parser::{json, SerializeOptions};
+    ///
+    /// let options = SerializeOptions { escape_forward_slashes: true, ..SerializeOptions::default() };
+    /// assert_eq!(json!(["</script>"]).to_string_with_options(&options), r#"["<\/script>"]"#);
+    /// ```
+    pub fn to_string_with_options(&self, options: &SerializeOptions) -> String {
+        let mut out = String::new();
+        write_compact(&mut out, self, options).unwrap();
+        out
+    }
+
+    /// Formats the value as human-readable JSON, indenting each nesting level
+    /// by `indent` spaces.
+    ///
+    /// Every array element and object member goes on its own line, and
+    /// members are written as `"key": value`. Empty arrays and objects stay on
+    /// one line as `[]` and `{}`. Object members are written in the order of
+    /// their [`Map`], which for a parsed value is document order,
+    /// so the output is the same on every run; use
+    /// [`SerializeOptions::sort_keys`] to sort them instead.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        self.to_string_indented(&" ".repeat(indent))
+    }
+
+    /// Formats the value like [`to_string_pretty`](JsonValue::to_string_pretty),
+    /// repeating `indent` once per nesting level, so it can indent with tabs
+    /// as well as spaces.
+    pub fn to_string_indented(&self, indent: &str) -> String {
+        self.to_string_indented_with_options(indent, &PLAIN)
+    }
+
+    /// Formats the value like
+    /// [`to_string_indented`](JsonValue::to_string_indented), adjusted by
+    /// `options`.
+    ///
+    /// ```
+    /// use json_parser::{json, SerializeOptions};
+    ///
+    /// let options = SerializeOptions { sort_keys: true, ..SerializeOptions::default() };
+    /// let value = json!({"z": 1, "a": 2});
+    /// assert_eq!(value.to_string_indented_with_options(" ", &options), "{\n \"a\": 2,\n \"z\": 1\n}");
+    /// ```
+    pub fn to_string_indented_with_options(&self, indent: &str, options: &SerializeOptions) -> String {
+        let mut out = String::new();
+        write_pretty(&mut out, self, indent, 0, options).unwrap();
+        out
+    }
+
+    /// Writes the value to `w` as compact JSON, exactly as `to_string` would
+    /// format it, without building the string first.
+    ///
+    /// The output goes out in many small writes, so an unbuffered `w` is
+    /// best wrapped in a [`BufWriter`](std::io::BufWriter).
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        IoAdapter::run(w, |w| write_compact(w, self, &PLAIN))
+    }
+
+    /// Writes the value to `w` like [`write_to`](JsonValue::write_to), but
+    /// formatted as [`to_string_indented`](JsonValue::to_string_indented)
+    /// would with the same `indent`.
+    pub fn write_pretty_to<W: io::Write>(&self, w: &mut W, indent: &str) -> io::Result<()> {
+        IoAdapter::run(w, |w| write_pretty(w, self, indent, 0, &PLAIN))
+    }
+
+    /// Formats the value as canonical JSON, suitable for hashing or signing.
+    ///
+    /// The output is compact, object members are sorted by key (comparing
+    /// UTF-16 code units, as RFC 8785 does), and numbers are written in their
+    /// shortest form, so values that compare equal always produce the same
+    /// bytes, whatever order their members were inserted in.
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        write_canonical(&mut out, self).unwrap();
+        out
+    }
+
+    /// Returns a digest of the value's canonical form, as written by
+    /// [`to_canonical_string`](JsonValue::to_canonical_string), for keying
+    /// caches on document content.
+    ///
+    /// Documents that differ only in whitespace or member order get the same
+    /// digest, as do numbers with the same value however they were written,
+    /// so `1` and `1.0` hash alike. Integers too large for an `i64` keep all
+    /// their digits, so two of them only hash alike when they're equal. The
+    /// digest is the 64-bit FNV-1a hash of the canonical bytes, so it stays
+    /// the same across runs and platforms, but it isn't cryptographic: don't
+    /// rely on it against crafted input.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+        write_canonical(&mut hasher, self).unwrap();
+        hasher.0
+    }
+}
+
+// Lets the `fmt::Write` based writers below write to an `io::Write`. A
+// `fmt::Error` carries nothing, so the underlying I/O error is kept aside
+// until the writer gives up.
+struct IoAdapter<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<'w, W: io::Write> IoAdapter<'w, W> {
+    fn run(inner: &'w mut W, f: impl FnOnce(&mut Self) -> fmt::Result) -> io::Result<()> {
+        let mut adapter = IoAdapter { inner, error: None };
+        match f(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter.error.unwrap_or_else(|| io::Error::other("formatting failed"))),
+        }
+    }
+}
+
+impl<W: io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+// Hashes whatever is written to it, without keeping it.
+struct Fnv1a(u64);
+
+impl Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+fn write_compact<W: Write>(w: &mut W, value: &JsonValue, options: &SerializeOptions) -> fmt::Result {
+    match value {
+        JsonValue::Null => w.write_str("null"),
+        JsonValue::Bool(b) => write!(w, "{}", b),
+        JsonValue::Int(i) => write!(w, "{}", i),
+        JsonValue::Float(n) => match options.float_precision {
+            Some(digits) if n.is_finite() => write!(w, "{:.*}", digits, n),
+            _ => write_num(w, *n),
+        },
+        JsonValue::RawNum(s) | JsonValue::BigInt(s) => w.write_str(s),
+        JsonValue::Str(s) => write_str(w, s, options),
+        JsonValue::Array(items) => {
+            w.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_compact(w, item, options)?;
+            }
+            w.write_char(']')
+        }
+        JsonValue::Object(members) => {
+            w.write_char('{')?;
+            each_member(members, options, |i, key, value| {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_str(w, key, options)?;
+                w.write_char(':')?;
+                write_compact(w, value, options)
+            })?;
+            w.write_char('}')
+        }
+    }
+}
+
+fn write_pretty<W: Write>(
+    w: &mut W,
+    value: &JsonValue,
+    indent: &str,
+    level: usize,
+    options: &SerializeOptions,
+) -> fmt::Result {
+    match value {
+        JsonValue::Array(items) if !items.is_empty() => {
+            w.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_newline(w, indent, level + 1, options)?;
+                write_pretty(w, item, indent, level + 1, options)?;
+            }
+            write_newline(w, indent, level, options)?;
+            w.write_char(']')
+        }
+        JsonValue::Object(members) if !members.is_empty() => {
+            w.write_char('{')?;
+            each_member(members, options, |i, key, value| {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_newline(w, indent, level + 1, options)?;
+                write_str(w, key, options)?;
+                w.write_str(": ")?;
+                write_pretty(w, value, indent, level + 1, options)
+            })?;
+            write_newline(w, indent, level, options)?;
+            w.write_char('}')
+        }
+        scalar => write_compact(w, scalar, options),
+    }
+}
+
+// Calls `f` with the position, key and value of each member, in the order
+// `options` asks for. Only sorting needs the members collected first.
+fn each_member(
+    members: &Map,
+    options: &SerializeOptions,
+    mut f: impl FnMut(usize, &str, &JsonValue) -> fmt::Result,
+) -> fmt::Result {
+    if options.sort_keys {
+        let mut sorted: Vec<_> = members.iter().collect();
+        sorted.sort_unstable_by_key(|&(key, _)| key);
+        sorted.into_iter().enumerate().try_for_each(|(i, (key, value))| f(i, key, value))
+    } else {
+        members.iter().enumerate().try_for_each(|(i, (key, value))| f(i, key, value))
+    }
+}
+
+fn write_canonical<W: Write>(w: &mut W, value: &JsonValue) -> fmt::Result {
+    match value {
+        JsonValue::Float(n) => write_canonical_num(w, *n),
+        // Integers keep every digit, however large, and only numbers with a
+        // fraction or exponent go through `f64`.
+        JsonValue::RawNum(s) | JsonValue::BigInt(s) => match (integer_digits(s), s.parse::<f64>()) {
+            (Some((negative, digits)), _) => {
+                if negative {
+                    w.write_char('-')?;
+                }
+                w.write_str(digits)
+            }
+            (None, Ok(n)) => write_canonical_num(w, n),
+            _ => w.write_str(s),
+        },
+        JsonValue::Array(items) => {
+            w.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_canonical(w, item)?;
+            }
+            w.write_char(']')
+        }
+        JsonValue::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            w.write_char('{')?;
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_str(w, key, &PLAIN)?;
+                w.write_char(':')?;
+                write_canonical(w, value)?;
+            }
+            w.write_char('}')
+        }
+        scalar => write!(w, "{}", scalar),
+    }
+}
+
+// Follows the ECMAScript number-to-string rules: negative zero is `0`, and
+// only very large or very small magnitudes switch to exponent notation.
+fn write_canonical_num<W: Write>(w: &mut W, n: f64) -> fmt::Result {
+    if n == 0.0 {
+        return w.write_char('0');
+    }
+    let magnitude = n.abs();
+    if !n.is_finite() {
+        return write_num(w, n);
+    }
+    if (1e-6..1e21).contains(&magnitude) {
+        return write!(w, "{}", n);
+    }
+    let formatted = format!("{:e}", n);
+    match formatted.split_once('e') {
+        Some((mantissa, exp)) if !exp.starts_with('-') => write!(w, "{}e+{}", mantissa, exp),
+        _ => w.write_str(&formatted),
+    }
+}
+
+fn write_newline<W: Write>(w: &mut W, indent: &str, level: usize, options: &SerializeOptions) -> fmt::Result {
+    w.write_str(match options.line_ending {
+        LineEnding::Lf => "\n",
+        LineEnding::Crlf => "\r\n",
+    })?;
+    for _ in 0..level {
+        w.write_str(indent)?;
+    }
+    Ok(())
+}
+
+// Writes the shortest digits that read back as exactly `n`, which is what
+// std's float formatting produces. A whole number keeps a `.0` so that it
+// parses back as a float rather than an integer, and very large or small
+// magnitudes use exponent notation rather than long runs of zeros.
+fn write_num<W: Write>(w: &mut W, n: f64) -> fmt::Result {
+    let magnitude = n.abs();
+    if !n.is_finite() {
+        w.write_str("null")
+    } else if magnitude != 0.0 && !(1e-5..1e16).contains(&magnitude) {
+        write!(w, "{:e}", n)
+    } else if n.fract() == 0.0 {
+        write!(w, "{:.1}", n)
+    } else {
+        write!(w, "{}", n)
+    }
+}
+
+fn write_str<W: Write>(w: &mut W, s: &str, options: &SerializeOptions) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '/' if options.escape_forward_slashes => w.write_str("\\/")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            '\u{08}' => w.write_str("\\b")?,
+            '\u{0C}' => w.write_str("\\f")?,
+            c if c < '\u{20}' || c == '\u{7f}' => write!(w, "\\u{:04x}", c as u32)?,
+            c if options.ascii_only && !c.is_ascii() => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(w, "\\u{:04x}", unit)?;
+                }
+            }
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
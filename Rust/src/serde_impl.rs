@@ -0,0 +1,109 @@
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{JsonValue, Map};
+
+/// Writes the value in the serde data model: `null` as a unit, and arrays
+/// and objects as sequences and maps, members in order.
+///
+/// A `RawNum` or `BigInt` is written as an integer when it is one that fits
+/// an `i64` or `u64`, and otherwise as an `f64`, which may round it.
+impl Serialize for JsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonValue::Null => serializer.serialize_unit(),
+            JsonValue::Bool(b) => serializer.serialize_bool(*b),
+            JsonValue::Int(n) => serializer.serialize_i64(*n),
+            JsonValue::Float(n) => serializer.serialize_f64(*n),
+            JsonValue::RawNum(s) | JsonValue::BigInt(s) => match (s.parse(), s.parse()) {
+                (Ok(n), _) => serializer.serialize_i64(n),
+                (_, Ok(n)) => serializer.serialize_u64(n),
+                _ => serializer.serialize_f64(self.as_f64().unwrap_or(f64::NAN)),
+            },
+            JsonValue::Str(s) => serializer.serialize_str(s),
+            JsonValue::Array(items) => serializer.collect_seq(items),
+            JsonValue::Object(members) => serializer.collect_map(members.iter()),
+        }
+    }
+}
+
+/// Reads any self-describing value, such as one from `serde_json` or a YAML
+/// library. Integers too large for an `i64` become `BigInt`s, and a map
+/// that repeats a key keeps the last value, as parsing does.
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<JsonValue, D::Error> {
+        JsonValue::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(b))
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Int(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<JsonValue, E> {
+        self.visit_u128(n.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, n: i128) -> Result<JsonValue, E> {
+        Ok(i64::try_from(n).map_or_else(|_| JsonValue::BigInt(n.to_string()), JsonValue::Int))
+    }
+
+    fn visit_u128<E: de::Error>(self, n: u128) -> Result<JsonValue, E> {
+        Ok(i64::try_from(n).map_or_else(|_| JsonValue::BigInt(n.to_string()), JsonValue::Int))
+    }
+
+    fn visit_f64<E: de::Error>(self, n: f64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Float(n))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut members = Map::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((key, value)) = map.next_entry()? {
+            members.insert(key, value);
+        }
+        Ok(JsonValue::Object(members))
+    }
+}
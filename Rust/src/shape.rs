@@ -0,0 +1,54 @@
+use crate::JsonValue;
+
+/// An expected structure for a value, checked with
+/// [`JsonValue::matches_shape`].
+///
+/// ```
+/// use json_parser::{json, Shape};
+///
+/// let shape = Shape::Object(vec![
+///     ("name".to_string(), Shape::String),
+///     ("tags".to_string(), Shape::Array(Box::new(Shape::String))),
+/// ]);
+/// assert!(json!({"name": "x", "tags": ["a"], "extra": 1}).matches_shape(&shape));
+/// assert!(!json!({"name": "x", "tags": [1]}).matches_shape(&shape));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Matches any value.
+    Any,
+    Null,
+    Bool,
+    /// Matches any number: `Int`, `Float`, `RawNum` or `BigInt`.
+    Number,
+    String,
+    /// An array whose elements all match the inner shape. An empty array
+    /// always matches.
+    Array(Box<Shape>),
+    /// An object that has every listed member, each matching its shape.
+    /// Members that aren't listed are allowed and ignored.
+    Object(Vec<(String, Shape)>),
+}
+
+impl JsonValue {
+    /// Returns `true` if the value has the structure `shape` describes.
+    pub fn matches_shape(&self, shape: &Shape) -> bool {
+        match shape {
+            Shape::Any => true,
+            Shape::Null => self.is_null(),
+            Shape::Bool => self.is_bool(),
+            Shape::Number => self.is_number(),
+            Shape::String => self.is_string(),
+            Shape::Array(element) => match self {
+                JsonValue::Array(items) => items.iter().all(|item| item.matches_shape(element)),
+                _ => false,
+            },
+            Shape::Object(members) => match self {
+                JsonValue::Object(object) => {
+                    members.iter().all(|(key, shape)| object.get(key).is_some_and(|value| value.matches_shape(shape)))
+                }
+                _ => false,
+            },
+        }
+    }
+}
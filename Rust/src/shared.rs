@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use crate::{JsonValue, Map};
+
+/// A JSON value whose children are reference counted, so that subtrees can
+/// be shared between documents and cloning never copies more than one level.
+///
+/// Wrap a whole document in an `Rc` to make cloning it O(1). Converting from
+/// a [`JsonValue`] keeps object members in order, and
+/// [`to_value`](JsonValueRc::to_value) converts back.
+///
+/// ```
+/// use std::rc::Rc;
+/// use json_parser::{json, JsonValueRc};
+///
+/// let shared = Rc::new(JsonValueRc::from(json!({"big": [1, 2, 3]})));
+/// let a = JsonValueRc::Array(vec![shared.clone()]);
+/// let b = JsonValueRc::Object(vec![("copy".to_string(), shared.clone())]);
+/// assert!(Rc::ptr_eq(&a.get_index(0).unwrap(), &b.get("copy").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueRc {
+    Null,
+    Int(i64),
+    Float(f64),
+    RawNum(String),
+    BigInt(String),
+    Bool(bool),
+    Str(String),
+    Array(Vec<Rc<JsonValueRc>>),
+    Object(Vec<(String, Rc<JsonValueRc>)>),
+}
+
+impl JsonValueRc {
+    /// Looks up a member of an object, returning `None` if the value isn't an
+    /// object or has no such member.
+    pub fn get(&self, key: &str) -> Option<&Rc<JsonValueRc>> {
+        match self {
+            JsonValueRc::Object(members) => members.iter().rev().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns element `i` of an array, or `None` if the value isn't an array
+    /// or is too short.
+    pub fn get_index(&self, i: usize) -> Option<&Rc<JsonValueRc>> {
+        match self {
+            JsonValueRc::Array(items) => items.get(i),
+            _ => None,
+        }
+    }
+
+    /// Copies the value, and everything shared beneath it, into an owned
+    /// [`JsonValue`].
+    pub fn to_value(&self) -> JsonValue {
+        match self {
+            JsonValueRc::Null => JsonValue::Null,
+            JsonValueRc::Int(i) => JsonValue::Int(*i),
+            JsonValueRc::Float(f) => JsonValue::Float(*f),
+            JsonValueRc::RawNum(s) => JsonValue::RawNum(s.clone()),
+            JsonValueRc::BigInt(s) => JsonValue::BigInt(s.clone()),
+            JsonValueRc::Bool(b) => JsonValue::Bool(*b),
+            JsonValueRc::Str(s) => JsonValue::Str(s.clone()),
+            JsonValueRc::Array(items) => JsonValue::Array(items.iter().map(|item| item.to_value()).collect()),
+            JsonValueRc::Object(members) => {
+                let members: Map = members.iter().map(|(key, value)| (key.clone(), value.to_value())).collect();
+                JsonValue::Object(members)
+            }
+        }
+    }
+}
+
+impl From<JsonValue> for JsonValueRc {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Null => JsonValueRc::Null,
+            JsonValue::Int(i) => JsonValueRc::Int(i),
+            JsonValue::Float(f) => JsonValueRc::Float(f),
+            JsonValue::RawNum(s) => JsonValueRc::RawNum(s),
+            JsonValue::BigInt(s) => JsonValueRc::BigInt(s),
+            JsonValue::Bool(b) => JsonValueRc::Bool(b),
+            JsonValue::Str(s) => JsonValueRc::Str(s),
+            JsonValue::Array(items) => JsonValueRc::Array(items.into_iter().map(|item| Rc::new(item.into())).collect()),
+            JsonValue::Object(members) => {
+                JsonValueRc::Object(members.into_iter().map(|(key, value)| (key, Rc::new(value.into()))).collect())
+            }
+        }
+    }
+}
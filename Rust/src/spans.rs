@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+use crate::parser::{Builder, Number, RawStr};
+use crate::{JsonValue, Map};
+
+/// A value along with the byte range of the source it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+/// A parsed JSON document in which every value records where it came from,
+/// as returned by [`parse_spanned`](crate::parse_spanned).
+///
+/// A span covers the value's own text, from its first byte to just past its
+/// last, so a string's span includes its quotes and a container's its
+/// brackets. Object members are kept in document order, duplicates included,
+/// like [`JsonValueRef`](crate::JsonValueRef) does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedValue {
+    Null,
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Array(Vec<Spanned<SpannedValue>>),
+    Object(Vec<(String, Spanned<SpannedValue>)>),
+}
+
+impl SpannedValue {
+    /// Looks up a member of an object, returning the last one with the key,
+    /// or `None` if the value isn't an object or has no such member.
+    pub fn get(&self, key: &str) -> Option<&Spanned<SpannedValue>> {
+        match self {
+            SpannedValue::Object(members) => members.iter().rev().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Drops the spans, leaving the [`JsonValue`] that
+    /// [`parse`](crate::parse) would have returned.
+    pub fn into_value(self) -> JsonValue {
+        match self {
+            SpannedValue::Null => JsonValue::Null,
+            SpannedValue::Int(i) => JsonValue::Int(i),
+            SpannedValue::Float(f) => JsonValue::Float(f),
+            SpannedValue::Bool(b) => JsonValue::Bool(b),
+            SpannedValue::Str(s) => JsonValue::Str(s),
+            SpannedValue::Array(items) => {
+                JsonValue::Array(items.into_iter().map(|item| item.value.into_value()).collect())
+            }
+            SpannedValue::Object(members) => {
+                let members: Map = members.into_iter().map(|(key, value)| (key, value.value.into_value())).collect();
+                JsonValue::Object(members)
+            }
+        }
+    }
+}
+
+/// Builds a [`SpannedValue`] tree, measuring offsets from the start of
+/// `input`.
+pub(crate) struct SpanBuilder<'a> {
+    pub(crate) input: &'a str,
+}
+
+impl SpanBuilder<'_> {
+    // Spans are filled in by `span` once the value is complete.
+    fn unspanned(value: SpannedValue) -> Spanned<SpannedValue> {
+        Spanned { value, span: 0..0 }
+    }
+}
+
+impl<'a> Builder<'a> for SpanBuilder<'a> {
+    type Value = Spanned<SpannedValue>;
+    type Array = Vec<Spanned<SpannedValue>>;
+    type Object = Vec<(String, Spanned<SpannedValue>)>;
+
+    fn null(&self) -> Self::Value {
+        Self::unspanned(SpannedValue::Null)
+    }
+
+    fn bool(&self, b: bool) -> Self::Value {
+        Self::unspanned(SpannedValue::Bool(b))
+    }
+
+    fn number(&self, lexeme: &'a str) -> Self::Value {
+        Self::unspanned(match Number::from_lexeme(lexeme) {
+            Number::Int(i) => SpannedValue::Int(i),
+            Number::Float(f) => SpannedValue::Float(f),
+        })
+    }
+
+    fn non_finite(&self, n: f64) -> Self::Value {
+        Self::unspanned(SpannedValue::Float(n))
+    }
+
+    fn string(&self, s: RawStr<'a>) -> Self::Value {
+        Self::unspanned(SpannedValue::Str(s.decode().into_owned()))
+    }
+
+    fn array(&self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&self, array: &mut Self::Array, value: Self::Value) {
+        array.push(value);
+    }
+
+    fn finish_array(&self, array: Self::Array) -> Self::Value {
+        Self::unspanned(SpannedValue::Array(array))
+    }
+
+    fn object(&self) -> Self::Object {
+        Vec::new()
+    }
+
+    fn member(&self, object: &mut Self::Object, key: RawStr<'a>, value: Self::Value) {
+        object.push((key.decode().into_owned(), value));
+    }
+
+    fn finish_object(&self, object: Self::Object) -> Self::Value {
+        Self::unspanned(SpannedValue::Object(object))
+    }
+
+    fn span(&self, value: Self::Value, start: &'a str, end: &'a str) -> Self::Value {
+        let offset = |rest: &str| self.input.len() - rest.len();
+        Spanned { span: offset(start)..offset(end), ..value }
+    }
+}
@@ -0,0 +1,124 @@
+use std::cell::Cell;
+
+use crate::parser::{Builder, RawStr, ValueBuilder};
+use crate::{JsonValue, Map};
+
+/// Size and shape figures gathered while parsing a document, as returned by
+/// [`parse_with_stats`](crate::parse_with_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// How deeply arrays and objects were nested, counted the same way as
+    /// [`ParseOptions::max_depth`](crate::ParseOptions::max_depth): a
+    /// document that is a scalar has depth 0, `[]` has depth 1.
+    pub max_depth: usize,
+    /// The number of values of any kind, containers included. Values are
+    /// counted as they're parsed, so one that a repeated key later replaced
+    /// still counts.
+    pub nodes: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    pub strings: usize,
+    pub numbers: usize,
+}
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], counting as it goes.
+#[derive(Default)]
+pub(crate) struct StatsBuilder {
+    stats: Cell<ParseStats>,
+    depth: Cell<usize>,
+}
+
+impl StatsBuilder {
+    pub(crate) fn stats(&self) -> ParseStats {
+        self.stats.get()
+    }
+
+    fn count(&self, f: impl FnOnce(&mut ParseStats)) {
+        let mut stats = self.stats.get();
+        stats.nodes += 1;
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+
+    fn enter(&self) {
+        let depth = self.depth.get() + 1;
+        self.depth.set(depth);
+        let mut stats = self.stats.get();
+        stats.max_depth = stats.max_depth.max(depth);
+        self.stats.set(stats);
+    }
+
+    fn leave(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+impl<'a> Builder<'a> for &StatsBuilder {
+    type Value = JsonValue;
+    type Array = Vec<JsonValue>;
+    type Object = Map;
+
+    fn null(&self) -> JsonValue {
+        self.count(|_| {});
+        ValueBuilder.null()
+    }
+
+    fn bool(&self, b: bool) -> JsonValue {
+        self.count(|_| {});
+        ValueBuilder.bool(b)
+    }
+
+    fn number(&self, lexeme: &'a str) -> JsonValue {
+        self.count(|stats| stats.numbers += 1);
+        ValueBuilder.number(lexeme)
+    }
+
+    fn raw_number(&self, lexeme: &'a str) -> JsonValue {
+        self.count(|stats| stats.numbers += 1);
+        ValueBuilder.raw_number(lexeme)
+    }
+
+    fn non_finite(&self, n: f64) -> JsonValue {
+        self.count(|stats| stats.numbers += 1);
+        ValueBuilder.non_finite(n)
+    }
+
+    fn string(&self, s: RawStr<'a>) -> JsonValue {
+        self.count(|stats| stats.strings += 1);
+        ValueBuilder.string(s)
+    }
+
+    fn array(&self) -> Vec<JsonValue> {
+        self.enter();
+        ValueBuilder.array()
+    }
+
+    fn push(&self, array: &mut Vec<JsonValue>, value: JsonValue) {
+        ValueBuilder.push(array, value);
+    }
+
+    fn reserve(&self, array: &mut Vec<JsonValue>, additional: usize) {
+        ValueBuilder.reserve(array, additional);
+    }
+
+    fn finish_array(&self, array: Vec<JsonValue>) -> JsonValue {
+        self.leave();
+        self.count(|stats| stats.arrays += 1);
+        ValueBuilder.finish_array(array)
+    }
+
+    fn object(&self) -> Map {
+        self.enter();
+        ValueBuilder.object()
+    }
+
+    fn member(&self, object: &mut Map, key: RawStr<'a>, value: JsonValue) {
+        ValueBuilder.member(object, key, value);
+    }
+
+    fn finish_object(&self, object: Map) -> JsonValue {
+        self.leave();
+        self.count(|stats| stats.objects += 1);
+        ValueBuilder.finish_object(object)
+    }
+}
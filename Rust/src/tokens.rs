@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use crate::parser::{self, Lexeme};
+use crate::{ParseError, Spanned};
+
+/// A lexical token of JSON, as yielded by [`tokenize`](crate::tokenize).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'a> {
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    /// A string literal, decoded. It borrows from the input unless it had
+    /// escapes; its span includes the quotes.
+    String(Cow<'a, str>),
+    /// A number, as written.
+    Number(&'a str),
+    Bool(bool),
+    Null,
+}
+
+pub(crate) fn tokenize(input: &str) -> impl Iterator<Item = Result<Spanned<Token<'_>>, ParseError>> + '_ {
+    // What's left to tokenize, or `None` once the input or an error is reached.
+    let mut rest = Some(input);
+    std::iter::from_fn(move || {
+        let start = parser::skip_whitespace(rest?);
+        if start.is_empty() {
+            rest = None;
+            return None;
+        }
+        let (after, lexeme) = match parser::lex(input, start) {
+            Ok(lexed) => lexed,
+            Err(e) => {
+                rest = None;
+                return Some(Err(e));
+            }
+        };
+        rest = Some(after);
+        let value = match lexeme {
+            Lexeme::Punct('{') => Token::LBrace,
+            Lexeme::Punct('}') => Token::RBrace,
+            Lexeme::Punct('[') => Token::LBracket,
+            Lexeme::Punct(']') => Token::RBracket,
+            Lexeme::Punct(':') => Token::Colon,
+            Lexeme::Punct(_) => Token::Comma,
+            Lexeme::Str(raw) => Token::String(raw.decode()),
+            Lexeme::Number(lexeme) => Token::Number(lexeme),
+            Lexeme::Bool(b) => Token::Bool(b),
+            Lexeme::Null => Token::Null,
+        };
+        let span = input.len() - start.len()..input.len() - after.len();
+        Some(Ok(Spanned { value, span }))
+    })
+}
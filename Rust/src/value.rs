@@ -0,0 +1,733 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+use std::str::FromStr;
+
+use crate::{pointer, KeyCase, Map, ParseError};
+
+/// A parsed JSON document.
+///
+/// Equality is structural: objects compare equal regardless of key order,
+/// and floats use `f64` equality, so a `Float(NAN)` is never equal to
+/// anything, itself included.
+///
+/// Values can be hashed, to serve as `HashMap` keys or be deduplicated in a
+/// `HashSet`, and `Eq` is implemented for that. Hashing agrees with
+/// equality: an object's hash doesn't depend on its key order, and `0.0`
+/// and `-0.0` hash alike. The exception is NaN, since a value holding one
+/// isn't equal to itself and so can never be found again as a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Int(i64),
+    Float(f64),
+    /// A number kept exactly as it was written, which only
+    /// [`ParseOptions::preserve_number_strings`](crate::ParseOptions::preserve_number_strings)
+    /// produces.
+    RawNum(String),
+    /// An integer too large for `i64`, as its exact digits, which only
+    /// [`ParseOptions::big_integers`](crate::ParseOptions::big_integers)
+    /// produces.
+    BigInt(String),
+    Bool(bool),
+    Str(String),
+    Array(Vec<JsonValue>),
+    Object(Map),
+}
+
+/// The error returned when a value is modified in a way only another
+/// variant supports, such as pushing onto an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+impl JsonValue {
+    /// Returns `true` if the value is `Null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Returns `true` if the value is a `Bool`.
+    pub fn is_bool(&self) -> bool {
+        matches!(self, JsonValue::Bool(_))
+    }
+
+    /// Returns `true` if the value is a number: an `Int`, `Float`, `RawNum`
+    /// or `BigInt`.
+    pub fn is_number(&self) -> bool {
+        matches!(self, JsonValue::Int(_) | JsonValue::Float(_) | JsonValue::RawNum(_) | JsonValue::BigInt(_))
+    }
+
+    /// Returns `true` if the value is a `Str`.
+    pub fn is_string(&self) -> bool {
+        matches!(self, JsonValue::Str(_))
+    }
+
+    /// Returns `true` if the value is an `Array`.
+    pub fn is_array(&self) -> bool {
+        matches!(self, JsonValue::Array(_))
+    }
+
+    /// Returns `true` if the value is an `Object`.
+    pub fn is_object(&self) -> bool {
+        matches!(self, JsonValue::Object(_))
+    }
+
+    /// Returns the string if the value is a `Str`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the string in UTF-16 code units, as
+    /// JavaScript's `length` counts it, if the value is a `Str`. Characters
+    /// outside the Basic Multilingual Plane count twice, once for each half
+    /// of their surrogate pair. For the length in UTF-8 bytes, use
+    /// [`as_str`](JsonValue::as_str) and `len`.
+    pub fn string_utf16_len(&self) -> Option<usize> {
+        self.as_str().map(|s| s.chars().map(char::len_utf16).sum())
+    }
+
+    /// Returns the boolean if the value is a `Bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            JsonValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if the value is an `Array`.
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the members if the value is an `Object`.
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            JsonValue::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Takes the string out of a `Str`, or hands the value back unchanged.
+    pub fn into_string(self) -> Result<String, JsonValue> {
+        match self {
+            JsonValue::Str(s) => Ok(s),
+            other => Err(other),
+        }
+    }
+
+    /// Takes the elements out of an `Array`, or hands the value back
+    /// unchanged.
+    pub fn into_array(self) -> Result<Vec<JsonValue>, JsonValue> {
+        match self {
+            JsonValue::Array(items) => Ok(items),
+            other => Err(other),
+        }
+    }
+
+    /// Takes the members out of an `Object`, or hands the value back
+    /// unchanged.
+    pub fn into_object(self) -> Result<Map, JsonValue> {
+        match self {
+            JsonValue::Object(members) => Ok(members),
+            other => Err(other),
+        }
+    }
+
+    /// Iterates over the members of an object in order. Yields nothing for
+    /// any other variant.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &JsonValue)> {
+        self.as_object().into_iter().flatten()
+    }
+
+    /// Iterates over the elements of an array. Yields nothing for any other
+    /// variant.
+    pub fn elements(&self) -> impl Iterator<Item = &JsonValue> {
+        self.as_array().into_iter().flatten()
+    }
+
+    /// Calls `f` on every value in the tree, depth-first, visiting each
+    /// parent before its children. Array elements are visited in order and
+    /// object members in their insertion order.
+    pub fn walk<F: FnMut(&JsonValue)>(&self, mut f: F) {
+        self.walk_with(&mut f);
+    }
+
+    fn walk_with<F: FnMut(&JsonValue)>(&self, f: &mut F) {
+        f(self);
+        match self {
+            JsonValue::Array(items) => items.iter().for_each(|item| item.walk_with(f)),
+            JsonValue::Object(members) => members.values().for_each(|value| value.walk_with(f)),
+            _ => {}
+        }
+    }
+
+    /// Rebuilds the tree with every scalar passed through `f`, along with
+    /// its path as a JSON Pointer. Returning `Some` puts the value it holds
+    /// in the scalar's place, and returning `None` drops the scalar from its
+    /// array or object; a dropped scalar at the top level becomes `null`.
+    /// Arrays and objects themselves are kept, even if that leaves them
+    /// empty. Paths are those of the original document, so array indices
+    /// don't shift as earlier elements are dropped.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let doc = json!({"user": {"name": "张三", "password": "hunter2"}});
+    /// let redacted = doc.map_values(|path, value| {
+    ///     Some(if path.ends_with("/password") { json!("***") } else { value })
+    /// });
+    /// assert_eq!(redacted, json!({"user": {"name": "张三", "password": "***"}}));
+    /// ```
+    pub fn map_values<F: FnMut(&str, JsonValue) -> Option<JsonValue>>(self, mut f: F) -> JsonValue {
+        self.map_values_with(&mut String::new(), &mut f).unwrap_or(JsonValue::Null)
+    }
+
+    fn map_values_with<F>(self, path: &mut String, f: &mut F) -> Option<JsonValue>
+    where
+        F: FnMut(&str, JsonValue) -> Option<JsonValue>,
+    {
+        let parent = path.len();
+        let mut child = |path: &mut String, segment: &str, value: JsonValue| {
+            path.push('/');
+            path.push_str(segment);
+            let value = value.map_values_with(path, f);
+            path.truncate(parent);
+            value
+        };
+        match self {
+            JsonValue::Array(items) => Some(JsonValue::Array(
+                items.into_iter().enumerate().filter_map(|(i, item)| child(path, &i.to_string(), item)).collect(),
+            )),
+            JsonValue::Object(members) => Some(JsonValue::Object(
+                members
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let value = child(path, &pointer::escape(&key), value)?;
+                        Some((key, value))
+                    })
+                    .collect(),
+            )),
+            scalar => f(path, scalar),
+        }
+    }
+
+    /// Gathers every string in the tree, in the order [`walk`](JsonValue::walk)
+    /// visits them. With `include_keys`, each object key comes just before
+    /// the strings in its value.
+    pub fn collect_strings(&self, include_keys: bool) -> Vec<&str> {
+        let mut strings = Vec::new();
+        self.collect_strings_into(include_keys, &mut strings);
+        strings
+    }
+
+    fn collect_strings_into<'a>(&'a self, include_keys: bool, strings: &mut Vec<&'a str>) {
+        match self {
+            JsonValue::Str(s) => strings.push(s),
+            JsonValue::Array(items) => items.iter().for_each(|item| item.collect_strings_into(include_keys, strings)),
+            JsonValue::Object(members) => {
+                for (key, value) in members {
+                    if include_keys {
+                        strings.push(key);
+                    }
+                    value.collect_strings_into(include_keys, strings);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Takes the value out, leaving `null` in its place, as
+    /// [`Option::take`] does with `None`. This moves part of a tree
+    /// elsewhere without cloning it.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let mut doc = json!({"tags": ["a", "b"]});
+    /// let tags = doc.pointer_mut("/tags").unwrap().take();
+    /// assert_eq!(tags, json!(["a", "b"]));
+    /// assert_eq!(doc, json!({"tags": null}));
+    /// ```
+    pub fn take(&mut self) -> JsonValue {
+        std::mem::replace(self, JsonValue::Null)
+    }
+
+    /// Appends `value` to an array. Anything else is left alone and
+    /// reported as a [`TypeError`].
+    pub fn push(&mut self, value: JsonValue) -> Result<(), TypeError> {
+        self.extend([value])
+    }
+
+    /// Appends every value in `values` to an array, in order. Anything else
+    /// is left alone and reported as a [`TypeError`].
+    pub fn extend<I: IntoIterator<Item = JsonValue>>(&mut self, values: I) -> Result<(), TypeError> {
+        match self {
+            JsonValue::Array(items) => {
+                items.extend(values);
+                Ok(())
+            }
+            _ => Err(TypeError { expected: "an array", found: self.kind() }),
+        }
+    }
+
+    /// Sets the member `key` of an object to `value`, returning the value it
+    /// replaced. As with [`Map::insert`], a new key goes at the end and an
+    /// existing one keeps its place. Anything other than an object is left
+    /// alone and reported as a [`TypeError`].
+    pub fn insert(&mut self, key: impl Into<String>, value: JsonValue) -> Result<Option<JsonValue>, TypeError> {
+        match self {
+            JsonValue::Object(members) => Ok(members.insert(key.into(), value)),
+            _ => Err(TypeError { expected: "an object", found: self.kind() }),
+        }
+    }
+
+    /// Removes the member named `key` from an object and returns its value,
+    /// or returns `None` if there isn't one or the value isn't an object.
+    pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.remove(key),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value is an object with a member named `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.as_object().is_some_and(|members| members.contains_key(key))
+    }
+
+    /// Returns the member named `key` if the value is an object that has one.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the element at position `i` if the value is an array that long.
+    pub fn get_index(&self, i: usize) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Array(items) => items.get(i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `i64` if it is an `Int`, or a `Float` or
+    /// `RawNum` holding a whole number that `i64` can represent exactly. A
+    /// `BigInt` never fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            JsonValue::Int(i) => Some(i),
+            JsonValue::RawNum(ref s) => s.parse().ok().or_else(|| JsonValue::Float(s.parse().ok()?).as_i64()),
+            // -2^63 is exactly representable; 2^63 is the first value past i64::MAX.
+            JsonValue::Float(f)
+                if f.fract() == 0.0 && (-9.223_372_036_854_776e18..9.223_372_036_854_776e18).contains(&f) =>
+            {
+                Some(f as i64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `f64` if it is a number. Integers beyond 2^53
+    /// may be rounded.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            JsonValue::Int(i) => Some(i as f64),
+            JsonValue::Float(f) => Some(f),
+            JsonValue::RawNum(ref s) | JsonValue::BigInt(ref s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Converts the value to a number the way JavaScript's `Number()` would,
+    /// for input too sloppy to keep its numbers as numbers. This isn't part of
+    /// JSON; use [`as_f64`](JsonValue::as_f64) unless you need it.
+    ///
+    /// `null` is 0, `false` and `true` are 0 and 1, and a string holding a
+    /// decimal number, with any surrounding whitespace, is that number. A
+    /// blank string is 0, and `Infinity` and `-Infinity` are infinite. Where
+    /// JavaScript would give NaN, as it does for any other string or for an
+    /// array or object, this returns `None`.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// assert_eq!(json!(" 42 ").coerce_to_number(), Some(42.0));
+    /// assert_eq!(json!("42px").coerce_to_number(), None);
+    /// ```
+    pub fn coerce_to_number(&self) -> Option<f64> {
+        match self {
+            JsonValue::Null => Some(0.0),
+            JsonValue::Bool(b) => Some(f64::from(u8::from(*b))),
+            JsonValue::Str(s) => {
+                let s = s.trim();
+                match s {
+                    "" => Some(0.0),
+                    "Infinity" | "+Infinity" => Some(f64::INFINITY),
+                    "-Infinity" => Some(f64::NEG_INFINITY),
+                    // Rust also reads `inf` and `NaN`, which JavaScript doesn't.
+                    _ if s.bytes().all(|b| b.is_ascii_digit() || b".eE+-".contains(&b)) => s.parse().ok(),
+                    _ => None,
+                }
+            }
+            JsonValue::Array(_) | JsonValue::Object(_) => None,
+            number => number.as_f64(),
+        }
+    }
+
+    /// Returns whether the value is truthy in JavaScript, as a condition like
+    /// `if (value)` would see it: `null`, `false`, zero, NaN and the empty
+    /// string are false, and everything else is true, empty arrays and
+    /// objects included. Like [`coerce_to_number`](JsonValue::coerce_to_number),
+    /// this is for sloppy input rather than part of JSON.
+    pub fn coerce_to_bool(&self) -> bool {
+        match self {
+            JsonValue::Null => false,
+            JsonValue::Bool(b) => *b,
+            JsonValue::Str(s) => !s.is_empty(),
+            JsonValue::Array(_) | JsonValue::Object(_) => true,
+            number => number.as_f64().is_some_and(|n| n != 0.0 && !n.is_nan()),
+        }
+    }
+
+    /// Like `==`, but an `Int` and a `Float` holding the same number compare
+    /// equal, so `1` loosely equals `1.0`. This applies at any depth inside
+    /// arrays and objects; everything else is compared as `==` would.
+    pub fn loosely_eq(&self, other: &JsonValue) -> bool {
+        match (self, other) {
+            (&JsonValue::Int(i), float @ JsonValue::Float(_)) | (float @ JsonValue::Float(_), &JsonValue::Int(i)) => {
+                float.as_i64() == Some(i)
+            }
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.loosely_eq(b))
+            }
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| a.loosely_eq(b)))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Sorts every array in the tree that holds only scalars, for comparing
+    /// documents in which array order doesn't matter. Arrays holding an
+    /// array or object keep their order, though arrays nested in them are
+    /// still sorted.
+    ///
+    /// Scalars are ordered by kind and then by value: `null`, then `false`
+    /// before `true`, then numbers ascending by value whatever their
+    /// variant, compared exactly rather than as `f64`s, then strings by byte
+    /// value. A NaN sorts after every other number.
+    pub fn sort_scalar_arrays(&mut self) {
+        match self {
+            JsonValue::Array(items) if items.iter().all(JsonValue::is_scalar) => items.sort_by(scalar_order),
+            JsonValue::Array(items) => items.iter_mut().for_each(JsonValue::sort_scalar_arrays),
+            JsonValue::Object(members) => members.values_mut().for_each(JsonValue::sort_scalar_arrays),
+            _ => {}
+        }
+    }
+
+    /// Estimates how many bytes of heap memory the value holds, counting the
+    /// capacity of its strings and of the storage behind its arrays and
+    /// objects, and everything nested in them. The value itself, usually on
+    /// the stack or inside a parent, isn't counted. This is meant for sizing
+    /// caches, not accounting: allocator overhead is ignored, and a key that
+    /// [`ParseOptions::intern_keys`](crate::ParseOptions::intern_keys) shared
+    /// between objects is counted once for each of them.
+    pub fn estimated_heap_size(&self) -> usize {
+        match self {
+            JsonValue::Str(s) | JsonValue::RawNum(s) | JsonValue::BigInt(s) => s.capacity(),
+            JsonValue::Array(items) => {
+                items.capacity() * std::mem::size_of::<JsonValue>()
+                    + items.iter().map(JsonValue::estimated_heap_size).sum::<usize>()
+            }
+            JsonValue::Object(members) => members.estimated_heap_size(),
+            JsonValue::Null | JsonValue::Bool(_) | JsonValue::Int(_) | JsonValue::Float(_) => 0,
+        }
+    }
+
+    // Folds every object key in the tree, for `ParseOptions::normalize_keys`.
+    pub(crate) fn fold_keys(&mut self, case: KeyCase) {
+        match self {
+            JsonValue::Array(items) => items.iter_mut().for_each(|item| item.fold_keys(case)),
+            JsonValue::Object(members) => members.fold_keys(case),
+            _ => {}
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, JsonValue::Array(_) | JsonValue::Object(_))
+    }
+}
+
+// Splits `lexeme` into its sign and digits if it's an integer, written
+// without a fraction or exponent, dropping leading zeros so that equal
+// integers always split the same way. Zero is never negative.
+pub(crate) fn integer_digits(lexeme: &str) -> Option<(bool, &str)> {
+    let (negative, digits) = match lexeme.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, lexeme.strip_prefix('+').unwrap_or(lexeme)),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match digits.trim_start_matches('0') {
+        "" => Some((false, "0")),
+        digits => Some((negative, digits)),
+    }
+}
+
+// The order `sort_scalar_arrays` puts scalars in.
+fn scalar_order(a: &JsonValue, b: &JsonValue) -> Ordering {
+    let rank = |v: &JsonValue| match v {
+        JsonValue::Null => 0,
+        JsonValue::Bool(_) => 1,
+        JsonValue::Int(_) | JsonValue::Float(_) | JsonValue::RawNum(_) | JsonValue::BigInt(_) => 2,
+        _ => 3,
+    };
+    match (a, b) {
+        (JsonValue::Bool(a), JsonValue::Bool(b)) => a.cmp(b),
+        (JsonValue::Str(a), JsonValue::Str(b)) => a.cmp(b),
+        _ if rank(a) == 2 && rank(b) == 2 => match (Number::of(a), Number::of(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        },
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+// A number as `scalar_order` compares it: exactly, so that the order stays
+// consistent however each number is held, rather than rounding them all to
+// `f64`.
+enum Number<'a> {
+    Int(i64),
+    // An integer outside the range of `i64`, as its sign and digits.
+    Big(bool, &'a str),
+    // Never NaN.
+    Float(f64),
+}
+
+impl<'a> Number<'a> {
+    fn of(value: &'a JsonValue) -> Option<Self> {
+        let number = match value {
+            JsonValue::Int(n) => Number::Int(*n),
+            JsonValue::Float(n) => Number::Float(*n),
+            JsonValue::RawNum(s) | JsonValue::BigInt(s) => match (integer_digits(s), s.parse()) {
+                (Some(_), Ok(n)) => Number::Int(n),
+                (Some((negative, digits)), Err(_)) => Number::Big(negative, digits),
+                (None, _) => Number::Float(s.parse().ok()?),
+            },
+            _ => return None,
+        };
+        match number {
+            Number::Float(n) if n.is_nan() => None,
+            number => Some(number),
+        }
+    }
+
+    fn cmp(&self, other: &Number) -> Ordering {
+        // 2^63, the first float past the end of `i64`.
+        const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => a.cmp(b),
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(b).unwrap(),
+            (&Number::Big(a_negative, a), &Number::Big(b_negative, b)) => {
+                let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(b));
+                match (a_negative, b_negative) {
+                    (false, false) => magnitude,
+                    (true, true) => magnitude.reverse(),
+                    _ => b_negative.cmp(&a_negative),
+                }
+            }
+            // Every `Big` is beyond every `Int`, on the side of its sign.
+            (Number::Int(_), &Number::Big(negative, _)) => {
+                if negative {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            }
+            (&Number::Int(a), &Number::Float(b)) => {
+                if b >= LIMIT {
+                    Ordering::Less
+                } else if b < -LIMIT {
+                    Ordering::Greater
+                } else {
+                    // In range, the whole part converts exactly, and the
+                    // fraction settles a tie.
+                    let whole = b.trunc();
+                    a.cmp(&(whole as i64)).then_with(|| whole.partial_cmp(&b).unwrap())
+                }
+            }
+            (&Number::Big(negative, _), &Number::Float(b)) => {
+                if b.is_infinite() {
+                    0.0.partial_cmp(&b).unwrap()
+                } else if b.abs() < LIMIT {
+                    if negative {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                } else {
+                    // Floats this large are whole numbers, so their exact
+                    // digits compare with the others'.
+                    let digits = format!("{:.0}", b);
+                    let (b_negative, b) = integer_digits(&digits).unwrap();
+                    self.cmp(&Number::Big(b_negative, b))
+                }
+            }
+            (a, b) => b.cmp(a).reverse(),
+        }
+    }
+}
+
+static NULL: JsonValue = JsonValue::Null;
+
+impl Eq for JsonValue {}
+
+impl Hash for JsonValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            JsonValue::Null => {}
+            JsonValue::Int(n) => n.hash(state),
+            // Adding zero turns -0.0 into 0.0, which it equals.
+            JsonValue::Float(n) => (n + 0.0).to_bits().hash(state),
+            JsonValue::RawNum(s) | JsonValue::BigInt(s) | JsonValue::Str(s) => s.hash(state),
+            JsonValue::Bool(b) => b.hash(state),
+            JsonValue::Array(items) => items.hash(state),
+            JsonValue::Object(members) => members.hash(state),
+        }
+    }
+}
+
+/// Looks up an object member by key.
+///
+/// Indexing never panics: a missing key, or indexing into anything other than
+/// an object, yields a reference to `JsonValue::Null`. This lets lookups chain
+/// through optional parts of a document, as in `value["address"]["city"]`.
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Looks up an array element by position.
+///
+/// Like indexing by key, an out-of-range index or a non-array value yields
+/// `JsonValue::Null` rather than panicking.
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, i: usize) -> &JsonValue {
+        self.get_index(i).unwrap_or(&NULL)
+    }
+}
+
+/// Consumes an array, yielding its elements by value. Any other variant
+/// yields nothing, as with [`elements`](JsonValue::elements).
+impl IntoIterator for JsonValue {
+    type Item = JsonValue;
+    type IntoIter = std::vec::IntoIter<JsonValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_array().unwrap_or_default().into_iter()
+    }
+}
+
+impl From<()> for JsonValue {
+    fn from(_: ()) -> Self {
+        JsonValue::Null
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty)*) => {$(
+        impl From<$t> for JsonValue {
+            fn from(n: $t) -> Self {
+                JsonValue::Int(n.into())
+            }
+        }
+    )*};
+}
+
+from_integer!(i8 i16 i32 i64 u8 u16 u32);
+
+impl From<f32> for JsonValue {
+    fn from(n: f32) -> Self {
+        JsonValue::Float(n.into())
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(n: f64) -> Self {
+        JsonValue::Float(n)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::Str(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::Str(s)
+    }
+}
+
+impl From<Vec<JsonValue>> for JsonValue {
+    fn from(items: Vec<JsonValue>) -> Self {
+        JsonValue::Array(items)
+    }
+}
+
+impl From<Map> for JsonValue {
+    fn from(members: Map) -> Self {
+        JsonValue::Object(members)
+    }
+}
+
+/// Builds an object from a `HashMap`. The members take on the map's
+/// (unspecified) iteration order.
+impl From<HashMap<String, JsonValue>> for JsonValue {
+    fn from(members: HashMap<String, JsonValue>) -> Self {
+        JsonValue::Object(members.into_iter().collect())
+    }
+}
+
+/// Parses a complete JSON document, exactly like [`parse`](crate::parse).
+impl FromStr for JsonValue {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        crate::parse(s)
+    }
+}
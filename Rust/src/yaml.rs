@@ -0,0 +1,107 @@
+//! A YAML-like rendering of values, for reading rather than for parsing.
+
+use crate::JsonValue;
+
+impl JsonValue {
+    /// Renders the value as YAML-style indented lines: each object member as
+    /// `key: value` and each array element as `- value`, with nested arrays
+    /// and objects indented by two spaces under their key, or continuing on
+    /// the line of their `-`.
+    ///
+    /// This is a readable subset of YAML rather than a full emitter. Empty
+    /// arrays and objects are written as `[]` and `{}`, and other scalars as
+    /// they are in JSON, which YAML reads the same way, except that strings
+    /// and keys made only of letters, digits, spaces and `_-./` are left
+    /// unquoted where that can't change their meaning. NaN and the
+    /// infinities are written as `null`, as in JSON. There is no trailing
+    /// newline.
+    ///
+    /// ```
+    /// use json_parser::json;
+    ///
+    /// let value = json!({"name": "web", "ports": [80, 443], "tls": {"enabled": true}});
+    /// assert_eq!(value.to_yaml_like(), "name: web\nports:\n  - 80\n  - 443\ntls:\n  enabled: true");
+    /// ```
+    pub fn to_yaml_like(&self) -> String {
+        let mut out = String::new();
+        if is_block(self) {
+            write_block(&mut out, self, 0);
+        } else {
+            write_scalar(&mut out, self);
+        }
+        out
+    }
+}
+
+// Whether `value` is written over lines of its own.
+fn is_block(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Array(items) => !items.is_empty(),
+        JsonValue::Object(members) => !members.is_empty(),
+        _ => false,
+    }
+}
+
+// Writes a non-empty array or object as lines indented by `indent`,
+// without the indentation of the first line, which is already written.
+fn write_block(out: &mut String, value: &JsonValue, indent: usize) {
+    match value {
+        JsonValue::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    newline(out, indent);
+                }
+                out.push_str("- ");
+                if is_block(item) {
+                    write_block(out, item, indent + 2);
+                } else {
+                    write_scalar(out, item);
+                }
+            }
+        }
+        JsonValue::Object(members) => {
+            for (i, (key, value)) in members.iter().enumerate() {
+                if i > 0 {
+                    newline(out, indent);
+                }
+                write_str(out, key);
+                out.push(':');
+                if is_block(value) {
+                    newline(out, indent + 2);
+                    write_block(out, value, indent + 2);
+                } else {
+                    out.push(' ');
+                    write_scalar(out, value);
+                }
+            }
+        }
+        scalar => write_scalar(out, scalar),
+    }
+}
+
+fn newline(out: &mut String, indent: usize) {
+    out.push('\n');
+    out.extend(std::iter::repeat_n(' ', indent));
+}
+
+fn write_scalar(out: &mut String, value: &JsonValue) {
+    match value {
+        JsonValue::Str(s) => write_str(out, s),
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+// Writes `s` bare if YAML would read it back as the same string, and as a
+// JSON string, which YAML reads as a double-quoted one, otherwise.
+fn write_str(out: &mut String, s: &str) {
+    let reserved = ["null", "true", "false", "yes", "no", "on", "off", "y", "n"];
+    let plain = s.chars().all(|c| c.is_alphanumeric() || " _-./".contains(c))
+        && s.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '/')
+        && !s.ends_with(' ')
+        && !reserved.iter().any(|word| s.eq_ignore_ascii_case(word));
+    if plain {
+        out.push_str(s);
+    } else {
+        out.push_str(&JsonValue::Str(s.to_string()).to_string());
+    }
+}
@@ -0,0 +1,19 @@
+use json_parser::{parse, parse_with_options, ParseOptions};
+
+#[path = "../benches/inputs/mod.rs"]
+mod inputs;
+
+#[test]
+fn bench_inputs_parse_the_same_however_they_are_spaced() {
+    let comments = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+    for (name, input) in inputs::all() {
+        let value = parse(&input).unwrap();
+        assert_eq!(parse(&value.to_string()).unwrap(), value, "{}", name);
+        assert_eq!(parse(&value.to_string_indented("\t")).unwrap(), value, "{}", name);
+        assert_eq!(parse_with_options(&input, &comments).unwrap(), value, "{}", name);
+
+        // None of the inputs has a raw newline inside a string.
+        let commented = format!("/* {} */ {}", name, input.replace('\n', " // note\n"));
+        assert_eq!(parse_with_options(&commented, &comments).unwrap(), value, "{}", name);
+    }
+}
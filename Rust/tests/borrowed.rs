@@ -0,0 +1,42 @@
+mod common;
+
+use std::borrow::Cow;
+
+use common::allocations_during;
+use json_parser::{parse, parse_borrowed, JsonValueRef};
+
+#[test]
+fn plain_strings_are_not_copied() {
+    let count = 1000;
+    let input = format!("[{}]", vec![r#""some plain text""#; count].join(","));
+
+    let (value, borrowed_allocations) = allocations_during(|| parse_borrowed(&input).unwrap());
+    let JsonValueRef::Array(items) = &value else { panic!("expected an array") };
+    assert_eq!(items.len(), count);
+    assert!(items.iter().all(|item| matches!(item, JsonValueRef::Str(Cow::Borrowed("some plain text")))));
+
+    // Only the array itself allocates, growing a logarithmic number of times.
+    let (_, owned_allocations) = allocations_during(|| parse(&input).unwrap());
+    assert!(borrowed_allocations < 20, "{} allocations", borrowed_allocations);
+    assert!(owned_allocations >= count, "{} allocations", owned_allocations);
+}
+
+#[test]
+fn escaped_strings_are_decoded() {
+    let value = parse_borrowed(r#"{"a\nb": "tab\there", "plain": "x", "emoji": "\uD83D\uDE00"}"#).unwrap();
+    let JsonValueRef::Object(members) = &value else { panic!("expected an object") };
+    assert!(matches!(&members[0].0, Cow::Owned(key) if key == "a\nb"));
+    assert!(matches!(&members[0].1, JsonValueRef::Str(Cow::Owned(s)) if s == "tab\there"));
+    assert!(matches!(&members[1].0, Cow::Borrowed("plain")));
+    assert_eq!(value.get("emoji").and_then(JsonValueRef::as_str), Some("😀"));
+}
+
+#[test]
+fn matches_the_owned_parse() {
+    let input = r#"{"name": "张三", "n": [1, -2.5, true, null], "nested": {"k": "v\"q"}, "name": "李四"}"#;
+    let value = parse_borrowed(input).unwrap();
+    assert_eq!(value.get("name").and_then(JsonValueRef::as_str), Some("李四"));
+    assert_eq!(value.into_owned(), parse(input).unwrap());
+    assert!(parse_borrowed("[1, 2").is_err());
+    assert!(parse_borrowed("\"\\q\"").is_err());
+}
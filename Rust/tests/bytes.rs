@@ -0,0 +1,58 @@
+use json_parser::{parse, parse_bytes, parse_bytes_with_options, InvalidUtf8Policy, ParseError, ParseOptions};
+
+#[test]
+fn parses_valid_utf8_bytes() {
+    let input = r#"{"nickname": "张三", "emoji": "😀", "n": [1, 2]}"#;
+    assert_eq!(parse_bytes(input.as_bytes()).unwrap(), parse(input).unwrap());
+}
+
+#[test]
+fn rejects_invalid_continuation_byte_in_string() {
+    // 0xE5 starts a three-byte sequence, but 0x41 ('A') is not a continuation byte.
+    let err = parse_bytes(b"{\"city\": \"\xE5\x41\"}").unwrap_err();
+    let ParseError::InvalidUtf8 { position } = err else {
+        panic!("expected InvalidUtf8, got {:?}", err);
+    };
+    assert_eq!(position.offset, 10);
+}
+
+#[test]
+fn reports_syntax_errors_in_valid_bytes() {
+    assert!(matches!(parse_bytes(b"[1, 2"), Err(ParseError::UnexpectedEof { .. })));
+    assert!(matches!(parse_bytes(b"[1] 2"), Err(ParseError::TrailingData { .. })));
+}
+
+fn policy(on_invalid_utf8: InvalidUtf8Policy) -> ParseOptions {
+    ParseOptions { on_invalid_utf8, ..ParseOptions::default() }
+}
+
+#[test]
+fn invalid_utf8_is_an_error_by_default() {
+    let input = b"{\"city\": \"\xE5\x41\"}";
+    let err = parse_bytes_with_options(input, &policy(InvalidUtf8Policy::Error)).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidUtf8 { .. }), "{:?}", err);
+    assert_eq!(ParseOptions::default().on_invalid_utf8, InvalidUtf8Policy::Error);
+}
+
+#[test]
+fn invalid_utf8_can_be_replaced() {
+    let replace = policy(InvalidUtf8Policy::Replace);
+    let value = parse_bytes_with_options(b"{\"city\": \"\xE5\x41\", \"n\": [1, \"\xFF\"]}", &replace).unwrap();
+    assert_eq!(value, parse("{\"city\": \"\u{FFFD}A\", \"n\": [1, \"\u{FFFD}\"]}").unwrap());
+
+    // Outside a string the replacement character is still a syntax error.
+    let err = parse_bytes_with_options(b"[1, \xFF]", &replace).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: '\u{FFFD}', .. }), "{:?}", err);
+}
+
+#[test]
+fn encoded_surrogates_never_reach_a_string() {
+    // U+D800 encoded as if it were a character, as CESU-8 and WTF-8 do. It
+    // isn't UTF-8, so the policy decides, and replacing leaves no surrogate
+    // behind for the serializer to trip over.
+    let input = b"[\"\xED\xA0\x80\"]";
+    assert!(matches!(parse_bytes(input), Err(ParseError::InvalidUtf8 { .. })));
+    let value = parse_bytes_with_options(input, &policy(InvalidUtf8Policy::Replace)).unwrap();
+    assert_eq!(value.to_string(), "[\"\u{FFFD}\u{FFFD}\u{FFFD}\"]");
+    assert_eq!(parse(&value.to_string()).unwrap(), value);
+}
@@ -0,0 +1,157 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+// A file in the temp directory holding `contents`, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: impl AsRef<[u8]>) -> TempFile {
+        let path = std::env::temp_dir().join(format!("json_parser_cli_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_JsonParser")).args(args).output().unwrap()
+}
+
+fn run_with_stdin(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_JsonParser"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn pretty_prints_a_file_by_default() {
+    let file = TempFile::new("pretty.json", r#"{"a": [1, 2], "b": "北京"}"#);
+    for args in [&[file.path()][..], &["--pretty", file.path()]] {
+        let output = run(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(stdout(&output), "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": \"北京\"\n}\n");
+    }
+}
+
+#[test]
+fn minifies_stdin() {
+    let output = run_with_stdin(&["--minify"], "{ \"a\" : [ 1 , true ] }\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "{\"a\":[1,true]}\n");
+
+    let output = run_with_stdin(&["--minify", "-"], "[ null ]");
+    assert_eq!(stdout(&output), "[null]\n");
+}
+
+#[test]
+fn validates_without_output() {
+    let valid = TempFile::new("valid.json", "[1, 2, 3]");
+    let output = run(&["--validate", valid.path()]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let invalid = TempFile::new("invalid.json", "{\n  \"a\": tru\n}");
+    let output = run(&["--validate", invalid.path()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).contains("line 2, column 8"), "{}", stderr(&output));
+
+    let output = run_with_stdin(&["--validate"], &format!("{}1{}", "[".repeat(100), "]".repeat(100)));
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "");
+
+    // Errors are reported as parsing would report them, invalid UTF-8 included.
+    for (name, contents) in [("deep.json", "[".repeat(1000).into_bytes()), ("latin1.json", b"[\"caf\xe9\"]".to_vec())] {
+        let file = TempFile::new(name, contents);
+        let validated = run(&["--validate", file.path()]);
+        let parsed = run(&[file.path()]);
+        assert_eq!(validated.status.code(), Some(1));
+        assert_eq!(stderr(&validated), stderr(&parsed));
+    }
+}
+
+#[test]
+fn reports_errors_with_nonzero_status() {
+    let output = run_with_stdin(&[], "[1, 2");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "<stdin>: unexpected end of input at line 1, column 6\n");
+
+    let output = run(&["/nonexistent/input.json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("/nonexistent/input.json: failed to read input"));
+
+    let output = run(&["--frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("error: unknown option `--frobnicate`"));
+
+    let output = run(&["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("usage: JsonParser"));
+}
+
+#[test]
+fn prints_help_wherever_it_is_asked_for() {
+    for args in [&["--minify", "--help"][..], &["-h"], &["--indent", "4", "-h", "missing.json"]] {
+        let output = run(args);
+        assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+        assert!(stdout(&output).starts_with("usage: JsonParser"), "{:?}", args);
+        assert_eq!(stderr(&output), "");
+    }
+}
+
+#[test]
+fn indents_by_the_requested_amount() {
+    let file = TempFile::new("indent.json", r#"{"a": [1], "b": {}}"#);
+    let output = run(&["--indent", "4", file.path()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "{\n    \"a\": [\n        1\n    ],\n    \"b\": {}\n}\n");
+
+    let output = run(&[file.path(), "--indent", "tab"]);
+    assert_eq!(stdout(&output), "{\n\t\"a\": [\n\t\t1\n\t],\n\t\"b\": {}\n}\n");
+
+    let output = run(&["--indent", "0", file.path()]);
+    assert_eq!(stdout(&output), "{\n\"a\": [\n1\n],\n\"b\": {}\n}\n");
+
+    let output = run(&["--indent", "16", file.path()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with(&format!("{{\n{}\"a\"", " ".repeat(16))));
+}
+
+#[test]
+fn rejects_bad_indents() {
+    for (args, message) in [
+        (&["--indent", "four"][..], "error: invalid indent `four`: expected a number of spaces or `tab`"),
+        (&["--indent", "-1"], "error: invalid indent `-1`"),
+        (&["--indent", "17"], "error: invalid indent `17`: at most 16 spaces are allowed"),
+        (&["--indent", "99999999999"], "error: invalid indent `99999999999`: at most 16 spaces"),
+        (&["--indent"], "error: `--indent` needs a value"),
+    ] {
+        let output = run(args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(stderr(&output).starts_with(message), "{}", stderr(&output));
+    }
+}
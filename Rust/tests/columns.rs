@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use json_parser::{json, parse, ColumnLengthError, JsonValue, TypeError};
+
+#[test]
+fn pivots_records_into_columns() {
+    let columns = parse(r#"[{"a":1,"b":2},{"a":3}]"#).unwrap().to_columns().unwrap();
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns["a"], [json!(1), json!(3)]);
+    assert_eq!(columns["b"], [json!(2), json!(null)]);
+}
+
+#[test]
+fn fills_fields_missing_from_earlier_records() {
+    let records = json!([{"a": 1}, {}, {"b": [true], "a": null}]);
+    let columns = records.to_columns().unwrap();
+    assert_eq!(columns["a"], [json!(1), json!(null), json!(null)]);
+    assert_eq!(columns["b"], [json!(null), json!(null), json!([true])]);
+    assert!(json!([]).to_columns().unwrap().is_empty());
+}
+
+#[test]
+fn rejects_anything_but_arrays_of_objects() {
+    assert_eq!(json!({"a": 1}).to_columns(), Err(TypeError { expected: "an array", found: "an object" }));
+    assert_eq!(json!([{"a": 1}, 2]).to_columns(), Err(TypeError { expected: "an object", found: "a number" }));
+}
+
+#[test]
+fn columns_pivot_back_into_records() {
+    let columns = HashMap::from([
+        ("name".to_string(), vec![json!("张三"), json!("李四")]),
+        ("age".to_string(), vec![json!(30), json!(null)]),
+    ]);
+    let records = JsonValue::from_columns(columns.clone()).unwrap();
+    assert_eq!(records, json!([{"age": 30, "name": "张三"}, {"age": null, "name": "李四"}]));
+    assert_eq!(records.to_columns().unwrap(), columns);
+
+    let records = parse(r#"[{"a": 1, "b": [2]}, {"a": 3, "b": {}}]"#).unwrap();
+    assert_eq!(JsonValue::from_columns(records.to_columns().unwrap()).unwrap(), records);
+    assert_eq!(JsonValue::from_columns(HashMap::new()).unwrap(), json!([]));
+}
+
+#[test]
+fn columns_of_different_lengths_are_rejected() {
+    let columns = HashMap::from([
+        ("a".to_string(), vec![json!(1), json!(2)]),
+        ("b".to_string(), vec![json!(3)]),
+        ("c".to_string(), vec![json!(4), json!(5)]),
+    ]);
+    let err = JsonValue::from_columns(columns).unwrap_err();
+    assert_eq!(err, ColumnLengthError { key: "b".to_string(), len: 1, expected: 2 });
+    assert_eq!(err.to_string(), "column \"b\" has length 1, expected 2");
+}
@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, HashMap};
+
+use json_parser::{
+    json, parse, parse_with_options, ConvertError, FromJson, IntoJson, JsonValue, LookupError, ParseOptions,
+};
+
+#[test]
+fn converts_arrays_into_vecs() {
+    let value = parse("[1, 2.5, -3e2]").unwrap();
+    assert_eq!(Vec::<f64>::from_json(&value), Ok(vec![1.0, 2.5, -300.0]));
+    assert_eq!(Vec::<i32>::from_json(&json!([1, 2.0, 3])), Ok(vec![1, 2, 3]));
+    assert_eq!(
+        Vec::<f64>::from_json(&json!([1, "two"])),
+        Err(ConvertError::WrongType { expected: "a number", found: "a string" })
+    );
+    assert_eq!(
+        Vec::<f64>::from_json(&json!({"a": 1})),
+        Err(ConvertError::WrongType { expected: "an array", found: "an object" })
+    );
+}
+
+#[test]
+fn converts_null_into_none() {
+    let value = json!({"name": "Ann", "nickname": null});
+    assert_eq!(Option::<String>::from_json(&value["name"]), Ok(Some("Ann".to_string())));
+    assert_eq!(Option::<String>::from_json(&value["nickname"]), Ok(None));
+    assert!(Option::<String>::from_json(&json!(1)).is_err());
+}
+
+#[test]
+fn checks_integer_ranges() {
+    assert_eq!(u8::from_json(&json!(255)), Ok(255));
+    assert_eq!(u8::from_json(&json!(256)), Err(ConvertError::OutOfRange { target: "u8" }));
+    assert_eq!(u64::from_json(&json!(-1)), Err(ConvertError::OutOfRange { target: "u64" }));
+    assert_eq!(i64::from_json(&json!(1e300)), Err(ConvertError::OutOfRange { target: "i64" }));
+    assert_eq!(i32::from_json(&json!(1.5)), Err(ConvertError::WrongType { expected: "an integer", found: "a number" }));
+    assert_eq!(ConvertError::OutOfRange { target: "u8" }.to_string(), "number out of range for u8");
+}
+
+#[test]
+fn converts_unsigned_integers_up_to_u64_max() {
+    let exact = ParseOptions { big_integers: true, ..ParseOptions::default() };
+    let raw = ParseOptions { preserve_number_strings: true, ..ParseOptions::default() };
+    for options in [&exact, &raw] {
+        let value =
+            parse_with_options("[18446744073709551615, 18446744073709551616, 9223372036854775808]", options).unwrap();
+        assert_eq!(u64::from_json(&value[0]), Ok(u64::MAX));
+        assert_eq!(usize::from_json(&value[0]), Ok(usize::MAX));
+        assert_eq!(u64::from_json(&value[1]), Err(ConvertError::OutOfRange { target: "u64" }));
+        assert_eq!(u64::from_json(&value[2]), Ok(1 << 63));
+        assert_eq!(i64::from_json(&value[2]), Err(ConvertError::OutOfRange { target: "i64" }));
+        assert_eq!(u32::from_json(&value[2]), Err(ConvertError::OutOfRange { target: "u32" }));
+    }
+    // Floats convert when they're whole and in range.
+    assert_eq!(u64::from_json(&json!(1e19)), Ok(10_000_000_000_000_000_000));
+    assert_eq!(u64::from_json(&json!(18446744073709551616.0)), Err(ConvertError::OutOfRange { target: "u64" }));
+    assert_eq!(u64::from_json(&JsonValue::RawNum("1.5e19".to_string())), Ok(15_000_000_000_000_000_000));
+    assert_eq!(
+        u64::from_json(&JsonValue::BigInt("-18446744073709551615".to_string())),
+        Err(ConvertError::OutOfRange { target: "u64" })
+    );
+}
+
+#[test]
+fn converts_objects_into_hash_maps() {
+    let map = HashMap::<String, bool>::from_json(&json!({"a": true, "b": false})).unwrap();
+    assert_eq!(map, HashMap::from([("a".to_string(), true), ("b".to_string(), false)]));
+    assert_eq!(
+        HashMap::<String, bool>::from_json(&json!({"a": null})).unwrap_err().to_string(),
+        "expected a boolean, found null"
+    );
+    assert_eq!(String::from_json(&JsonValue::Str("s".to_string())), Ok("s".to_string()));
+}
+
+#[test]
+fn converts_typed_data_into_values() {
+    let numbers: Vec<i64> = vec![1, -2, 3];
+    let value = numbers.into_json();
+    assert_eq!(value, json!([1, -2, 3]));
+    assert_eq!(value.to_string(), "[1,-2,3]");
+
+    let flags = HashMap::from([("on".to_string(), true), ("off".to_string(), false)]);
+    let value = flags.into_json();
+    assert_eq!(value, json!({"on": true, "off": false}));
+    assert_eq!(value.to_canonical_string(), r#"{"off":false,"on":true}"#);
+
+    let sorted = BTreeMap::from([("b".to_string(), vec![None, Some("x")]), ("a".to_string(), vec![])]);
+    assert_eq!(sorted.into_json().to_string(), r#"{"a":[],"b":[null,"x"]}"#);
+}
+
+#[test]
+fn into_json_round_trips_through_from_json() {
+    let original = vec![Some(1.5), None, Some(-2.0)];
+    assert_eq!(Vec::<Option<f64>>::from_json(&original.clone().into_json()), Ok(original));
+}
+
+#[test]
+fn gets_typed_values_by_pointer() {
+    let doc = parse(r#"{"nickname": "张三", "age": 30, "address": {"code": [200, 2000]}, "nothing": null}"#).unwrap();
+    assert_eq!(doc.get_as::<f64>("/age"), Ok(30.0));
+    assert_eq!(doc.get_as::<String>("/nickname").unwrap(), "张三");
+    assert_eq!(doc.get_as::<u16>("/address/code/1"), Ok(2000));
+    assert_eq!(doc.get_as::<Option<i64>>("/nothing"), Ok(None));
+
+    let mismatch = ConvertError::WrongType { expected: "a number", found: "a string" };
+    assert_eq!(
+        doc.get_as::<f64>("/nickname"),
+        Err(LookupError::Convert { path: "/nickname".to_string(), error: mismatch })
+    );
+    assert_eq!(
+        doc.get_as::<u8>("/address/code/1").unwrap_err().to_string(),
+        "number out of range for u8 at \"/address/code/1\""
+    );
+    for ptr in ["/missing", "/address/code/2", "age"] {
+        assert_eq!(doc.get_as::<Option<f64>>(ptr), Err(LookupError::NotFound { path: ptr.to_string() }));
+    }
+}
@@ -0,0 +1,47 @@
+use json_parser::{json, parse, parse_with_diagnostics, Diagnostic, KeyCase, ParseOptions};
+
+fn warn_duplicates() -> ParseOptions {
+    ParseOptions { warn_duplicate_keys: true, ..ParseOptions::default() }
+}
+
+fn duplicate(path: &str, key: &str) -> Diagnostic {
+    Diagnostic::DuplicateKey { path: path.to_string(), key: key.to_string() }
+}
+
+#[test]
+fn reports_duplicate_keys_with_their_path() {
+    let input = r#"{"a": [1, {"x": 1, "y": 2, "x": 3}], "b": {"c/d": {"k": 1, "k": 2, "k": 3}}, "a": null}"#;
+    let (value, diagnostics) = parse_with_diagnostics(input, &warn_duplicates()).unwrap();
+    assert_eq!(value, parse(input).unwrap());
+    assert_eq!(
+        diagnostics,
+        [duplicate("/a/1", "x"), duplicate("/b/c~1d", "k"), duplicate("/b/c~1d", "k"), duplicate("", "a")]
+    );
+}
+
+#[test]
+fn compares_keys_after_decoding_escapes() {
+    let (value, diagnostics) = parse_with_diagnostics(r#"[{"a": 1, "\u0061": 2}]"#, &warn_duplicates()).unwrap();
+    assert_eq!(value, json!([{"a": 2}]));
+    assert_eq!(diagnostics, [duplicate("/0", "a")]);
+}
+
+#[test]
+fn reports_nothing_unless_enabled() {
+    let input = r#"{"a": 1, "a": 2}"#;
+    let (value, diagnostics) = parse_with_diagnostics(input, &ParseOptions::default()).unwrap();
+    assert_eq!(value, json!({"a": 2}));
+    assert!(diagnostics.is_empty());
+
+    let (_, diagnostics) = parse_with_diagnostics(r#"{"a": 1, "b": {"a": 2}}"#, &warn_duplicates()).unwrap();
+    assert!(diagnostics.is_empty());
+    assert!(parse_with_diagnostics(r#"{"a": 1, "a": }"#, &warn_duplicates()).is_err());
+}
+
+#[test]
+fn folds_keys_after_reporting_them() {
+    let options = ParseOptions { normalize_keys: Some(KeyCase::Lower), ..warn_duplicates() };
+    let (value, diagnostics) = parse_with_diagnostics(r#"{"A": 1, "a": 2, "a": 3}"#, &options).unwrap();
+    assert_eq!(value, json!({"a": 3}));
+    assert_eq!(diagnostics, [duplicate("", "a")]);
+}
@@ -0,0 +1,90 @@
+use json_parser::{json, Change};
+
+#[test]
+fn equal_values_have_an_empty_diff() {
+    let value = json!({"a": [1, {"b": null}], "c": "d"});
+    assert_eq!(value.diff(&value.clone()), json!({}));
+    assert_eq!(json!(1).diff(&json!(1)), json!({}));
+}
+
+#[test]
+fn reports_added_removed_and_changed_members() {
+    let old = json!({
+        "name": "app",
+        "port": 80,
+        "debug": true,
+        "db": {"host": "localhost", "pool": 5}
+    });
+    let new = json!({
+        "name": "app",
+        "port": 8080,
+        "db": {"host": "db.internal", "pool": 5, "tls": true},
+        "workers": 4
+    });
+    assert_eq!(
+        old.diff(&new),
+        json!({
+            "added": {"workers": 4},
+            "removed": {"debug": true},
+            "changed": {
+                "port": {"from": 80, "to": 8080},
+                "db": {
+                    "added": {"tls": true},
+                    "changed": {"host": {"from": "localhost", "to": "db.internal"}}
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn compares_arrays_by_index() {
+    assert_eq!(
+        json!([1, 2, 3]).diff(&json!([1, 5])),
+        json!({"removed": {"2": 3}, "changed": {"1": {"from": 2, "to": 5}}})
+    );
+    assert_eq!(json!([1]).diff(&json!([1, [2]])), json!({"added": {"1": [2]}}));
+}
+
+#[test]
+fn type_changes_replace_the_value() {
+    assert_eq!(json!({"a": 1}).diff(&json!([1])), json!({"from": {"a": 1}, "to": [1]}));
+    assert_eq!(json!({"a": "1"}).diff(&json!({"a": 1})), json!({"changed": {"a": {"from": "1", "to": 1}}}));
+}
+
+#[test]
+fn lists_changes_with_their_paths() {
+    let old = json!({
+        "name": "app",
+        "port": 80,
+        "debug": true,
+        "db": {"host": "localhost", "pool": 5},
+        "a/b": [1, 2, 3],
+        "tags": "x"
+    });
+    let new = json!({
+        "name": "app",
+        "port": 8080,
+        "db": {"host": "db.internal", "pool": 5, "tls": true},
+        "a/b": [1, 20],
+        "tags": ["x"],
+        "workers": 4
+    });
+    let path = |p: &str| p.to_string();
+    assert_eq!(
+        old.changes(&new),
+        [
+            Change::Modified { path: path("/port"), old: json!(80), new: json!(8080) },
+            Change::Removed { path: path("/debug"), value: json!(true) },
+            Change::Modified { path: path("/db/host"), old: json!("localhost"), new: json!("db.internal") },
+            Change::Added { path: path("/db/tls"), value: json!(true) },
+            Change::Modified { path: path("/a~1b/1"), old: json!(2), new: json!(20) },
+            Change::Removed { path: path("/a~1b/2"), value: json!(3) },
+            Change::Modified { path: path("/tags"), old: json!("x"), new: json!(["x"]) },
+            Change::Added { path: path("/workers"), value: json!(4) },
+        ]
+    );
+
+    assert!(old.changes(&old.clone()).is_empty());
+    assert_eq!(json!(1).changes(&json!(2)), [Change::Modified { path: path(""), old: json!(1), new: json!(2) }]);
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::io;
+
+use json_parser::{parse, ParseError, Position};
+
+fn error_at(input: &str) -> Position {
+    parse(input).unwrap_err().position().unwrap()
+}
+
+#[test]
+fn reports_line_and_column() {
+    let input = "{\n  \"a\": 1,\n  \"b\": tru,\n  \"c\": 3\n}";
+    let position = error_at(input);
+    assert_eq!((position.line, position.column), (3, 8));
+    assert_eq!(position.offset, input.find("tru").unwrap());
+}
+
+#[test]
+fn reports_errors_inside_arrays() {
+    let position = error_at("[\n  1,\n  2,\n  x\n]");
+    assert_eq!((position.line, position.column), (4, 3));
+}
+
+#[test]
+fn reports_missing_colon() {
+    let position = error_at("{\n\n  \"key\" 1}");
+    assert_eq!((position.line, position.column), (3, 9));
+}
+
+#[test]
+fn reports_unterminated_containers_at_end_of_input() {
+    let input = "[1,\n 2";
+    let position = error_at(input);
+    assert_eq!(position.offset, input.len());
+    assert_eq!((position.line, position.column), (2, 3));
+}
+
+#[test]
+fn counts_columns_in_characters() {
+    let position = error_at("[\"北京\", ?]");
+    assert_eq!((position.line, position.column), (1, 8));
+}
+
+#[test]
+fn classifies_unexpected_characters() {
+    let cases = [("[1 2]", '2'), ("{\"a\" 1}", '1'), ("nul", 'n'), ("[1,]", ']'), ("\"a\tb\"", '\t')];
+    for (input, expected) in cases {
+        match parse(input) {
+            Err(ParseError::UnexpectedChar { found, .. }) => assert_eq!(found, expected, "{:?}", input),
+            other => panic!("expected UnexpectedChar for {:?}, got {:?}", input, other),
+        }
+    }
+}
+
+#[test]
+fn classifies_unexpected_end_of_input() {
+    for input in ["", "   ", "[1,", "{\"a\":", "\"open", "[[]"] {
+        assert!(matches!(parse(input), Err(ParseError::UnexpectedEof { .. })), "{:?}", input);
+    }
+}
+
+#[test]
+fn empty_documents_end_unexpectedly_at_their_end() {
+    for (input, line, column) in [("", 1, 1), ("   ", 1, 4), ("\n\t", 2, 2)] {
+        match parse(input) {
+            Err(ParseError::UnexpectedEof { position }) => {
+                assert_eq!(position.offset, input.len(), "{:?}", input);
+                assert_eq!((position.line, position.column), (line, column), "{:?}", input);
+            }
+            other => panic!("expected UnexpectedEof for {:?}, got {:?}", input, other),
+        }
+    }
+    assert_eq!(parse(" ").unwrap_err().to_string(), "unexpected end of input at line 1, column 2");
+}
+
+#[test]
+fn classifies_invalid_numbers() {
+    for (input, offset) in [("01", 1), ("-", 1), ("[1.]", 3), ("1e+", 3), ("-x", 1)] {
+        let err = parse(input).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidNumber { .. }), "{:?} gave {:?}", input, err);
+        assert_eq!(err.position().unwrap().offset, offset, "{:?}", input);
+    }
+}
+
+#[test]
+fn classifies_invalid_escapes() {
+    for input in [r#""\q""#, r#""\u12G4""#, r#""\uD83D""#, r#""\uDE00""#, r#""trailing\"#] {
+        assert!(matches!(parse(input), Err(ParseError::InvalidEscape { .. })), "{:?}", input);
+    }
+    assert_eq!(parse(r#"["ok", "\x"]"#).unwrap_err().position().unwrap().offset, 9);
+}
+
+#[test]
+fn describes_errors_with_their_position() {
+    assert_eq!(parse("[1 2]").unwrap_err().to_string(), "unexpected character '2' at line 1, column 4");
+    assert_eq!(parse("[1,").unwrap_err().to_string(), "unexpected end of input at line 1, column 4");
+    assert_eq!(parse("01").unwrap_err().to_string(), "invalid number at line 1, column 2");
+    assert_eq!(parse("[\n  \"\\q\"]").unwrap_err().to_string(), "invalid escape sequence at line 2, column 5");
+    assert_eq!(parse("1\n\n 2").unwrap_err().to_string(), "trailing data after the JSON value at line 3, column 2");
+}
+
+#[test]
+fn works_as_a_std_error() {
+    fn parse_boxed(input: &str) -> Result<json_parser::JsonValue, Box<dyn Error>> {
+        Ok(parse(input)?)
+    }
+    let err = parse_boxed("{\n  \"a\": tru\n}").unwrap_err();
+    assert_eq!(err.to_string(), "unexpected character 't' at line 2, column 8");
+    assert!(err.source().is_none());
+
+    let io = ParseError::Io(io::Error::new(io::ErrorKind::UnexpectedEof, "pipe closed"));
+    assert_eq!(io.to_string(), "failed to read input: pipe closed");
+    assert!(io.source().is_some());
+}
+
+#[test]
+fn rejects_unquoted_keys_helpfully() {
+    let err = parse(r#"{name: "John"}"#).unwrap_err();
+    assert!(matches!(err, ParseError::UnquotedKey { .. }), "{:?}", err);
+    assert_eq!(err.to_string(), "object keys must be quoted strings at line 1, column 2");
+
+    let err = parse("{\"ok\": 1,\n  _private$1: 2}").unwrap_err();
+    assert!(matches!(err, ParseError::UnquotedKey { .. }), "{:?}", err);
+    assert_eq!(error_at("{\"ok\": 1,\n  _private$1: 2}"), Position { offset: 12, line: 2, column: 3 });
+
+    // Things that aren't identifiers are still just unexpected.
+    assert!(matches!(parse("{1: 2}"), Err(ParseError::UnexpectedChar { found: '1', .. })));
+    assert!(matches!(parse("{'a': 2}"), Err(ParseError::UnexpectedChar { found: '\'', .. })));
+}
+
+#[test]
+fn rejects_invalid_escapes_in_keys_like_values() {
+    for input in [r#"{"\uD800": 1}"#, r#"{"a\uDE00": 1}"#, r#"{"ok": {"\uD83DA": 1}}"#, r#"{"\q": 1}"#] {
+        let err = parse(input).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidEscape { .. }), "{:?} gave {:?}", input, err);
+        assert!(json_parser::validate(input).is_err(), "{:?}", input);
+        assert!(json_parser::parse_borrowed(input).is_err(), "{:?}", input);
+    }
+    assert_eq!(parse(r#"{"\uD800": 1}"#).unwrap_err().position().unwrap().offset, 3);
+
+    // NUL is a valid scalar value, in keys as in strings.
+    let value = parse(r#"{"a\u0000b": "\u0000"}"#).unwrap();
+    assert_eq!(value["a\0b"].as_str(), Some("\0"));
+}
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use json_parser::{parse_events, JsonEvent, JsonValueRef};
+
+fn events(input: &str) -> Vec<JsonEvent<'_>> {
+    let mut events = Vec::new();
+    parse_events(input, |event| events.push(event)).unwrap();
+    events
+}
+
+fn key(k: &str) -> JsonEvent<'_> {
+    JsonEvent::Key(Cow::Borrowed(k))
+}
+
+#[test]
+fn reports_events_in_document_order() {
+    let input = r#"{"name": "张三", "scores": [90, 1.5], "address": {"city": "北京", "zip": null}, "ok": true}"#;
+    assert_eq!(
+        events(input),
+        [
+            JsonEvent::StartObject,
+            key("name"),
+            JsonEvent::Value(JsonValueRef::Str(Cow::Borrowed("张三"))),
+            key("scores"),
+            JsonEvent::StartArray,
+            JsonEvent::Value(JsonValueRef::Int(90)),
+            JsonEvent::Value(JsonValueRef::Float(1.5)),
+            JsonEvent::EndArray,
+            key("address"),
+            JsonEvent::StartObject,
+            key("city"),
+            JsonEvent::Value(JsonValueRef::Str(Cow::Borrowed("北京"))),
+            key("zip"),
+            JsonEvent::Value(JsonValueRef::Null),
+            JsonEvent::EndObject,
+            key("ok"),
+            JsonEvent::Value(JsonValueRef::Bool(true)),
+            JsonEvent::EndObject,
+        ]
+    );
+}
+
+#[test]
+fn reports_empty_containers_and_scalars() {
+    assert_eq!(
+        events("[[], {}]"),
+        [
+            JsonEvent::StartArray,
+            JsonEvent::StartArray,
+            JsonEvent::EndArray,
+            JsonEvent::StartObject,
+            JsonEvent::EndObject,
+            JsonEvent::EndArray,
+        ]
+    );
+    assert_eq!(events(r#""a\nb""#), [JsonEvent::Value(JsonValueRef::Str(Cow::Owned("a\nb".to_string())))]);
+}
+
+#[test]
+fn stops_at_the_first_error() {
+    let mut seen = Vec::new();
+    let err = parse_events("[1, 2, x]", |event| seen.push(event)).unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 7);
+    assert_eq!(
+        seen,
+        [JsonEvent::StartArray, JsonEvent::Value(JsonValueRef::Int(1)), JsonEvent::Value(JsonValueRef::Int(2))]
+    );
+}
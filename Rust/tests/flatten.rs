@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use json_parser::{json, parse, JsonValue};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+fn flat(pairs: &[(&str, JsonValue)]) -> HashMap<String, JsonValue> {
+    pairs.iter().map(|(key, value)| (key.to_string(), value.clone())).collect()
+}
+
+#[test]
+fn flattens_the_sample_address() {
+    let sample = parse(SAMPLE).unwrap();
+    assert_eq!(
+        sample["address"].flatten(),
+        flat(&[
+            ("city", json!("北京")),
+            ("street", json!("中关村大街")),
+            ("code.0", json!(200)),
+            ("code.1", json!(2000)),
+        ])
+    );
+
+    let all = sample.flatten();
+    assert_eq!(all.len(), 10);
+    assert_eq!(all["address.city"], json!("北京"));
+    assert_eq!(all["scores.2"], json!(95));
+    assert_eq!(all["is_teacher"], json!(false));
+}
+
+#[test]
+fn keeps_empty_containers_and_scalar_roots() {
+    assert_eq!(
+        json!({"a": {}, "b": [], "c": [{}], "d": null}).flatten(),
+        flat(&[("a", json!({})), ("b", json!([])), ("c.0", json!({})), ("d", json!(null))])
+    );
+    assert_eq!(json!(7).flatten(), flat(&[("", json!(7))]));
+    assert_eq!(json!({}).flatten(), flat(&[("", json!({}))]));
+}
+
+#[test]
+fn unflatten_reverses_flatten() {
+    let sample = parse(SAMPLE).unwrap();
+    assert_eq!(JsonValue::unflatten(sample.flatten()), Ok(sample));
+
+    let doc = json!({"a": {}, "b": [[], [1, {"c": null}]], "d": "x"});
+    assert_eq!(JsonValue::unflatten(doc.flatten()), Ok(doc));
+    assert_eq!(JsonValue::unflatten(json!(7).flatten()), Ok(json!(7)));
+    assert_eq!(JsonValue::unflatten(json!([]).flatten()), Ok(json!([])));
+}
+
+#[test]
+fn unflatten_builds_arrays_from_indices() {
+    let gap = flat(&[("list.1", json!("b")), ("list.0", json!("a")), ("list.10", json!("k"))]);
+    assert!(JsonValue::unflatten(gap).is_err());
+
+    let items: Vec<_> = (0..12).map(|i| (format!("list.{}", i), json!(i))).collect();
+    let value = JsonValue::unflatten(items.into_iter().collect()).unwrap();
+    assert_eq!(value["list"], JsonValue::Array((0..12).map(JsonValue::from).collect()));
+
+    // Only canonical indices count; `01` is an ordinary member name.
+    let value = JsonValue::unflatten(flat(&[("a.01", json!(1)), ("a.x", json!(2))])).unwrap();
+    assert_eq!(value, json!({"a": {"01": 1, "x": 2}}));
+}
+
+#[test]
+fn unflatten_rejects_conflicting_paths() {
+    let conflict = |pairs: &[(&str, JsonValue)]| JsonValue::unflatten(flat(pairs)).unwrap_err().path;
+    assert_eq!(conflict(&[("a", json!(1)), ("a.b", json!(2))]), "a");
+    assert_eq!(conflict(&[("a.b.c", json!(1)), ("a.b", json!(2))]), "a.b");
+    assert_eq!(conflict(&[("a.0", json!(1)), ("a.x", json!(2))]), "a");
+    assert_eq!(conflict(&[("a.0", json!(1)), ("a.2", json!(2))]), "a");
+    assert_eq!(conflict(&[("", json!(1)), ("a", json!(2))]), "");
+
+    let err = JsonValue::unflatten(flat(&[("x", json!(1)), ("x.y", json!(2))])).unwrap_err();
+    assert_eq!(err.to_string(), "conflicting paths at \"x\"");
+}
@@ -0,0 +1,22 @@
+use json_parser::{json, parse, TypeError};
+
+#[test]
+fn encodes_flat_objects() {
+    let value = parse(r#"{"q": "北京", "page": 2}"#).unwrap();
+    assert_eq!(value.to_query_string().unwrap(), "q=%E5%8C%97%E4%BA%AC&page=2");
+
+    let value = json!({"a b": "x&y=z", "ratio": -2.5, "on": false, "none": null, "safe": "A-z.0_9~"});
+    assert_eq!(value.to_query_string().unwrap(), "a%20b=x%26y%3Dz&ratio=-2.5&on=false&none=&safe=A-z.0_9~");
+    assert_eq!(json!({}).to_query_string().unwrap(), "");
+}
+
+#[test]
+fn rejects_anything_but_flat_objects() {
+    assert_eq!(json!([1]).to_query_string(), Err(TypeError { expected: "an object", found: "an array" }));
+    assert_eq!(json!("q").to_query_string(), Err(TypeError { expected: "an object", found: "a string" }));
+    assert_eq!(
+        json!({"q": "x", "tags": ["a"]}).to_query_string(),
+        Err(TypeError { expected: "a scalar", found: "an array" })
+    );
+    assert!(json!({"nested": {"a": 1}}).to_query_string().is_err());
+}
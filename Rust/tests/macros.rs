@@ -0,0 +1,64 @@
+use json_parser::{json, parse, JsonValue};
+
+#[test]
+fn builds_literals() {
+    assert_eq!(json!(null), JsonValue::Null);
+    assert_eq!(json!(true), JsonValue::Bool(true));
+    assert_eq!(json!(false), JsonValue::Bool(false));
+    assert_eq!(json!(42), JsonValue::Int(42));
+    assert_eq!(json!(-2.5), JsonValue::Float(-2.5));
+    assert_eq!(json!("hi"), JsonValue::Str("hi".to_string()));
+    assert_eq!(json!([]), JsonValue::Array(vec![]));
+    assert_eq!(json!({}), parse("{}").unwrap());
+}
+
+#[test]
+fn matches_the_parsed_sample() {
+    let value = json!({
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    });
+    let parsed = parse(
+        r#"{"nickname": "张三", "age": 30, "is_teacher": false, "scores": [90, 85, 95],
+            "address": {"city": "北京", "street": "中关村大街", "code": [200, 2000]}}"#,
+    )
+    .unwrap();
+    assert_eq!(value, parsed);
+    assert_eq!(value.to_string(), parsed.to_string());
+}
+
+#[test]
+fn interpolates_expressions() {
+    let name = String::from("John");
+    let scores = vec![json!(1), json!(2)];
+    let key = "dynamic";
+    let value = json!({
+        "name": name.clone(),
+        "len": name.len() as i64,
+        "sum": 1 + 2,
+        "neg": -7,
+        "scores": scores,
+        (key): [null, true, {"nested": [[]]}, 0.5],
+    });
+    assert_eq!(
+        value,
+        parse(
+            r#"{"name": "John", "len": 4, "sum": 3, "neg": -7, "scores": [1, 2],
+                 "dynamic": [null, true, {"nested": [[]]}, 0.5]}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn accepts_trailing_commas() {
+    assert_eq!(json!([1, 2,]), parse("[1, 2]").unwrap());
+    assert_eq!(json!({"a": [1,], "b": null,}), parse(r#"{"a": [1], "b": null}"#).unwrap());
+}
@@ -0,0 +1,51 @@
+use json_parser::{parse, JsonValue, Map};
+
+#[test]
+fn round_trip_preserves_key_order() {
+    let input = r#"{"z":1,"a":2,"m":3}"#;
+    let value = parse(input).unwrap();
+    let keys: Vec<&str> = value.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["z", "a", "m"]);
+    assert_eq!(value.to_string(), input);
+    assert_eq!(value.to_string_pretty(2), "{\n  \"z\": 1,\n  \"a\": 2,\n  \"m\": 3\n}");
+}
+
+#[test]
+fn duplicate_key_keeps_first_position() {
+    let value = parse(r#"{"a":1,"b":2,"a":3}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"a":3,"b":2}"#);
+}
+
+#[test]
+fn insert_and_remove_keep_order() {
+    let mut map = Map::new();
+    assert_eq!(map.insert("one".to_string(), JsonValue::Int(1)), None);
+    map.insert("two".to_string(), JsonValue::Int(2));
+    map.insert("three".to_string(), JsonValue::Int(3));
+    assert_eq!(map.insert("one".to_string(), JsonValue::Int(10)), Some(JsonValue::Int(1)));
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["one", "two", "three"]);
+
+    assert_eq!(map.remove("two"), Some(JsonValue::Int(2)));
+    assert_eq!(map.remove("two"), None);
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["one", "three"]);
+    assert_eq!(map.get("three"), Some(&JsonValue::Int(3)));
+    assert_eq!(map.len(), 2);
+
+    map.insert("four".to_string(), JsonValue::Int(4));
+    let owned: Vec<(String, JsonValue)> = map.into_iter().collect();
+    assert_eq!(
+        owned,
+        [
+            ("one".to_string(), JsonValue::Int(10)),
+            ("three".to_string(), JsonValue::Int(3)),
+            ("four".to_string(), JsonValue::Int(4)),
+        ]
+    );
+}
+
+#[test]
+fn equality_ignores_order() {
+    let a: Map = [("x".to_string(), JsonValue::Int(1)), ("y".to_string(), JsonValue::Null)].into_iter().collect();
+    let b: Map = [("y".to_string(), JsonValue::Null), ("x".to_string(), JsonValue::Int(1))].into_iter().collect();
+    assert_eq!(a, b);
+}
@@ -0,0 +1,81 @@
+use json_parser::{json, JsonValue};
+
+#[test]
+fn adds_members() {
+    let mut doc = json!({"a": 1});
+    doc.merge_patch(&json!({"b": 2}));
+    assert_eq!(doc, json!({"a": 1, "b": 2}));
+}
+
+#[test]
+fn merges_nested_objects() {
+    let mut doc = json!({"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"]});
+    doc.merge_patch(&json!({"title": "Hello!", "phoneNumber": "+01-123-456-7890", "author": {"familyName": null}, "tags": ["example"]}));
+    assert_eq!(
+        doc,
+        json!({"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "phoneNumber": "+01-123-456-7890"})
+    );
+}
+
+#[test]
+fn null_deletes_members() {
+    let mut doc = json!({"a": 1, "b": {"c": 2}});
+    doc.merge_patch(&json!({"a": null, "missing": null}));
+    assert_eq!(doc, json!({"b": {"c": 2}}));
+}
+
+#[test]
+fn non_object_patch_replaces_the_target() {
+    let mut doc = json!({"a": [1, 2]});
+    doc.merge_patch(&json!(42));
+    assert_eq!(doc, json!(42));
+
+    let mut doc = json!({"a": [1, 2]});
+    doc.merge_patch(&json!({"a": [3]}));
+    assert_eq!(doc, json!({"a": [3]}));
+}
+
+#[test]
+fn object_patch_replaces_non_object_target() {
+    let mut doc = json!([1, 2]);
+    doc.merge_patch(&json!({"a": {"b": null, "c": 1}}));
+    assert_eq!(doc, json!({"a": {"c": 1}}));
+
+    let mut doc = JsonValue::Null;
+    doc.merge_patch(&json!({}));
+    assert_eq!(doc, json!({}));
+}
+
+fn fragments() -> (JsonValue, JsonValue) {
+    (
+        json!({"name": "app", "plugins": ["a", "b"], "server": {"port": 80, "hosts": ["x"]}}),
+        json!({"plugins": ["c"], "server": {"tls": true, "hosts": ["y"]}, "debug": null}),
+    )
+}
+
+#[test]
+fn merge_concatenates_arrays() {
+    let (mut base, overlay) = fragments();
+    base.merge(&overlay, true);
+    assert_eq!(
+        base,
+        json!({"name": "app", "plugins": ["a", "b", "c"], "server": {"port": 80, "hosts": ["x", "y"], "tls": true}, "debug": null})
+    );
+}
+
+#[test]
+fn merge_replaces_arrays() {
+    let (mut base, overlay) = fragments();
+    base.merge(&overlay, false);
+    assert_eq!(
+        base,
+        json!({"name": "app", "plugins": ["c"], "server": {"port": 80, "hosts": ["y"], "tls": true}, "debug": null})
+    );
+}
+
+#[test]
+fn merge_lets_other_win_on_type_conflicts() {
+    let mut base = json!({"a": {"b": 1}, "c": 2, "d": [1]});
+    base.merge(&json!({"a": 5, "c": {"x": true}, "d": {"k": null}}), true);
+    assert_eq!(base, json!({"a": 5, "c": {"x": true}, "d": {"k": null}}));
+}
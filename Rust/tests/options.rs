@@ -0,0 +1,451 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use json_parser::{parse, parse_with_options, JsonValue, KeyCase, Limit, ParseError, ParseOptions};
+
+fn nested_arrays(depth: usize) -> String {
+    format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn default_depth_limit_is_128() {
+    assert!(parse(&nested_arrays(128)).is_ok());
+    assert!(matches!(parse(&nested_arrays(129)), Err(ParseError::DepthLimitExceeded { limit: 128, .. })));
+}
+
+#[test]
+fn depth_limit_is_configurable() {
+    let options = ParseOptions { max_depth: 3, ..ParseOptions::default() };
+    assert!(parse_with_options(r#"[{"a": [1]}]"#, &options).is_ok());
+
+    let err = parse_with_options(r#"[{"a": [{}]}]"#, &options).unwrap_err();
+    assert!(matches!(err, ParseError::DepthLimitExceeded { limit: 3, .. }));
+    assert_eq!(err.position().unwrap().offset, 8);
+}
+
+#[test]
+fn very_deep_input_fails_cleanly() {
+    let input = nested_arrays(100_000);
+    assert!(matches!(parse(&input), Err(ParseError::DepthLimitExceeded { .. })));
+}
+
+#[test]
+fn raised_depth_limit_parses_without_recursing() {
+    let depth = 100_000;
+    let options = ParseOptions { max_depth: depth, ..ParseOptions::default() };
+    let input = format!("{}{{\"a\": {{}}}}{}", r#"{"a": ["#.repeat(depth / 2 - 1), "]}".repeat(depth / 2 - 1));
+
+    // Dropping the value recursively could overflow the stack too, so take
+    // it apart one level at a time.
+    let mut value = parse_with_options(&input, &options).unwrap();
+    let mut levels = 0;
+    loop {
+        levels += 1;
+        value = match value {
+            JsonValue::Object(map) if map.contains_key("a") => map.into_iter().next().unwrap().1,
+            JsonValue::Array(mut items) => items.pop().unwrap(),
+            _ => break,
+        };
+    }
+    assert_eq!(levels, depth);
+
+    let options = ParseOptions { max_depth: depth - 1, ..options };
+    assert!(matches!(parse_with_options(&input, &options), Err(ParseError::DepthLimitExceeded { .. })));
+}
+
+#[test]
+fn scalars_do_not_count_towards_depth() {
+    let options = ParseOptions { max_depth: 0, ..ParseOptions::default() };
+    assert_eq!(parse_with_options("42", &options).unwrap(), JsonValue::Int(42));
+    assert!(parse_with_options("[]", &options).is_err());
+}
+
+fn with_comments() -> ParseOptions {
+    ParseOptions { allow_comments: true, ..ParseOptions::default() }
+}
+
+#[test]
+fn comments_are_skipped_when_enabled() {
+    let input = r#"
+        // leading comment
+        {
+            "name": "John", // trailing line comment
+            /* block comment */ "age": /* inline */ 30,
+            "scores": [90, /* multi
+                line */ 85]
+        }
+        /* trailing */
+    "#;
+    let value = parse_with_options(input, &with_comments()).unwrap();
+    assert_eq!(value, parse(r#"{"name": "John", "age": 30, "scores": [90, 85]}"#).unwrap());
+    assert!(parse(input).is_err());
+}
+
+#[test]
+fn comment_markers_inside_strings_are_content() {
+    let value =
+        parse_with_options(r#"{"url": "http://example.com", "c": "/* not a comment */"}"#, &with_comments()).unwrap();
+    assert_eq!(value["url"], JsonValue::Str("http://example.com".to_string()));
+    assert_eq!(value["c"], JsonValue::Str("/* not a comment */".to_string()));
+}
+
+#[test]
+fn unterminated_block_comment_is_an_error() {
+    assert!(parse_with_options("[1] /* never closed", &with_comments()).is_err());
+    assert!(parse_with_options("[1, / 2]", &with_comments()).is_err());
+}
+
+#[test]
+fn trailing_commas_are_rejected_by_default() {
+    for input in ["[1,2,]", r#"{"a":1,}"#] {
+        assert!(matches!(parse(input), Err(ParseError::UnexpectedChar { .. })), "{:?} should be rejected", input);
+    }
+}
+
+#[test]
+fn trailing_commas_are_accepted_when_enabled() {
+    let options = ParseOptions { allow_trailing_commas: true, ..ParseOptions::default() };
+    assert_eq!(parse_with_options("[1,2,]", &options).unwrap(), parse("[1,2]").unwrap());
+    assert_eq!(parse_with_options(r#"{"a":1,}"#, &options).unwrap(), parse(r#"{"a":1}"#).unwrap());
+    assert_eq!(parse_with_options("[\n  1,\n  [2, ],\n]", &options).unwrap(), parse("[1,[2]]").unwrap());
+
+    for input in ["[,]", "[1,,]", "{,}", r#"{"a":1,,}"#] {
+        assert!(parse_with_options(input, &options).is_err(), "{:?} should be rejected", input);
+    }
+}
+
+#[test]
+fn non_finite_tokens_are_rejected_by_default() {
+    for input in ["NaN", "Infinity", "-Infinity", "[1, NaN]"] {
+        assert!(parse(input).is_err(), "{:?} should be rejected", input);
+    }
+}
+
+#[test]
+fn non_finite_tokens_are_accepted_when_enabled() {
+    let options = ParseOptions { allow_non_finite: true, ..ParseOptions::default() };
+    let parse = |input| parse_with_options(input, &options).unwrap();
+
+    assert!(matches!(parse("NaN"), JsonValue::Float(f) if f.is_nan()));
+    assert_eq!(parse("Infinity"), JsonValue::Float(f64::INFINITY));
+    assert_eq!(parse("-Infinity"), JsonValue::Float(f64::NEG_INFINITY));
+    assert_eq!(parse("[-Infinity, 1, -2]").to_string(), "[null,1,-2]");
+    assert!(parse_with_options("-Inf", &options).is_err());
+    assert!(parse_with_options("nan", &options).is_err());
+}
+
+#[test]
+fn unquoted_keys_are_rejected_by_default() {
+    for input in [r#"{name: "John"}"#, "{a1: 2}", r#"{"a": {b: 1}}"#] {
+        assert!(matches!(parse(input), Err(ParseError::UnquotedKey { .. })), "{:?} should be rejected", input);
+    }
+}
+
+#[test]
+fn unquoted_keys_are_accepted_when_enabled() {
+    let options = ParseOptions { allow_unquoted_keys: true, ..ParseOptions::default() };
+    let parse = |input| parse_with_options(input, &options).unwrap();
+
+    assert_eq!(parse(r#"{name: "John"}"#), parse(r#"{"name": "John"}"#));
+    assert_eq!(parse("{a1: 2}")["a1"], JsonValue::Int(2));
+    assert_eq!(parse("{ _x: 1, $y: 2, \"z w\": 3, 名前: 4 }").to_string(), r#"{"_x":1,"$y":2,"z w":3,"名前":4}"#);
+    assert!(parse_with_options("{1a: 2}", &options).is_err());
+    assert!(parse_with_options("{a-b: 2}", &options).is_err());
+    assert!(parse_with_options("{a: b}", &options).is_err());
+}
+
+#[test]
+fn single_quotes_are_rejected_by_default() {
+    for input in ["'hello'", r#"{'a': 1}"#, r#"["ok", 'no']"#] {
+        assert!(parse(input).is_err(), "{:?} should be rejected", input);
+    }
+    assert!(parse(r#""it\'s""#).is_err());
+}
+
+#[test]
+fn single_quotes_are_accepted_when_enabled() {
+    let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+    let parse = |input| parse_with_options(input, &options).unwrap();
+
+    assert_eq!(parse("'hello'"), JsonValue::from("hello"));
+    assert_eq!(parse(r#"'it\'s a "quote"'"#), JsonValue::from(r#"it's a "quote""#));
+    assert_eq!(parse(r#"'\"\nA'"#), JsonValue::from("\"\nA"));
+    assert_eq!(parse(r#"{'a': 'x', "b": "y"}"#), parse(r#"{"a": "x", "b": "y"}"#));
+    assert_eq!(parse(r#""double still works""#), JsonValue::from("double still works"));
+
+    // `\'` only escapes inside a single-quoted string.
+    assert!(parse_with_options(r#""it\'s""#, &options).is_err());
+    assert!(parse_with_options("'unterminated", &options).is_err());
+    assert!(parse_with_options("'mixed\"", &options).is_err());
+}
+
+#[test]
+fn numbers_are_converted_by_default() {
+    assert!(matches!(parse("123456789012345678901234567890"), Ok(JsonValue::Float(_))));
+}
+
+#[test]
+fn number_strings_are_preserved_when_enabled() {
+    let options = ParseOptions { preserve_number_strings: true, ..ParseOptions::default() };
+    let parse = |input| parse_with_options(input, &options).unwrap();
+
+    let big = "123456789012345678901234567890";
+    assert_eq!(parse(big), JsonValue::RawNum(big.to_string()));
+    assert_eq!(parse(big).to_string(), big);
+
+    let input = r#"{"price":19.990,"id":9007199254740993,"exp":-1.5E+2,"nested":[0,-0]}"#;
+    assert_eq!(parse(input).to_string(), input);
+    assert_eq!(parse(input)["price"], JsonValue::RawNum("19.990".to_string()));
+    assert_eq!(parse(input)["id"].as_i64(), Some(9_007_199_254_740_993));
+    assert_eq!(parse(input)["exp"].as_f64(), Some(-150.0));
+    assert!(parse(input)["exp"].is_number());
+
+    // The grammar is enforced all the same.
+    assert!(parse_with_options("01", &options).is_err());
+}
+
+#[test]
+fn scalar_roots_are_allowed_by_default() {
+    assert_eq!(parse("42").unwrap(), JsonValue::Int(42));
+    assert_eq!(parse(" \"hi\" ").unwrap(), JsonValue::Str("hi".to_string()));
+}
+
+#[test]
+fn scalar_roots_are_rejected_when_containers_are_required() {
+    let options = ParseOptions { require_container_root: true, ..ParseOptions::default() };
+    let err = parse_with_options(" 42", &options).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: '4', .. }));
+    assert_eq!(err.position().unwrap().offset, 1);
+    assert!(parse_with_options("null", &options).is_err());
+    assert!(matches!(parse_with_options("", &options), Err(ParseError::UnexpectedEof { .. })));
+
+    // Only the root is restricted.
+    assert_eq!(parse_with_options("[42]", &options).unwrap(), parse("[42]").unwrap());
+    assert_eq!(parse_with_options(r#"{"a": "hi"}"#, &options).unwrap()["a"], JsonValue::Str("hi".to_string()));
+}
+
+fn limited() -> ParseOptions {
+    ParseOptions { max_string_length: 5, max_array_length: 3, max_object_entries: 2, ..ParseOptions::default() }
+}
+
+fn limit_error(input: &str) -> (Limit, usize, usize) {
+    match parse_with_options(input, &limited()) {
+        Err(ParseError::LimitExceeded { what, limit, position }) => (what, limit, position.offset),
+        other => panic!("expected LimitExceeded for {:?}, got {:?}", input, other),
+    }
+}
+
+#[test]
+fn size_limits_are_unlimited_by_default() {
+    let long = format!("[\"{}\", {}]", "x".repeat(100_000), vec!["0"; 100_000].join(","));
+    assert!(parse(&long).is_ok());
+}
+
+#[test]
+fn strings_over_the_limit_are_rejected() {
+    assert_eq!(parse_with_options(r#"["12345"]"#, &limited()).unwrap(), parse(r#"["12345"]"#).unwrap());
+    assert_eq!(limit_error(r#"["123456"]"#), (Limit::StringLength, 5, 1));
+    // Escapes count as written, and keys are limited too.
+    assert_eq!(limit_error(r#""\n\n\n""#), (Limit::StringLength, 5, 0));
+    assert_eq!(limit_error(r#"{"longkey": 1}"#), (Limit::StringLength, 5, 1));
+}
+
+#[test]
+fn arrays_and_objects_over_the_limit_are_rejected() {
+    assert!(parse_with_options("[1, 2, 3]", &limited()).is_ok());
+    assert_eq!(limit_error("[1, 2, 3, 4]"), (Limit::ArrayLength, 3, 10));
+    assert_eq!(limit_error("[[], [[1, 2, 3, 4]]]"), (Limit::ArrayLength, 3, 16));
+
+    assert!(parse_with_options(r#"{"a": 1, "b": 2}"#, &limited()).is_ok());
+    assert_eq!(limit_error(r#"{"a": 1, "a": 2, "a": 3}"#), (Limit::ObjectEntries, 2, 17));
+
+    let err = parse_with_options("[1, 2, 3, 4]", &limited()).unwrap_err();
+    assert_eq!(err.to_string(), "array exceeds the limit of 3 elements at line 1, column 11");
+}
+
+fn tagged(lexeme: &str) -> JsonValue {
+    JsonValue::RawNum(format!("#{}", lexeme))
+}
+
+#[test]
+fn number_handler_builds_every_number() {
+    let options = ParseOptions { number_handler: Some(Arc::new(tagged)), ..ParseOptions::default() };
+    let value = parse_with_options(r#"{"a": [1, -2.50e+3], "b": {"c": 0}, "s": "7"}"#, &options).unwrap();
+    assert_eq!(value["a"][0], JsonValue::RawNum("#1".to_string()));
+    assert_eq!(value["a"][1], JsonValue::RawNum("#-2.50e+3".to_string()));
+    assert_eq!(value["b"]["c"], JsonValue::RawNum("#0".to_string()));
+    assert_eq!(value["s"], JsonValue::Str("7".to_string()));
+
+    // It wins over the other number options, and works alongside the rest.
+    let options = ParseOptions { preserve_number_strings: true, big_integers: true, intern_keys: true, ..options };
+    let value = parse_with_options("[{\"n\": 12345678901234567890}]", &options).unwrap();
+    assert_eq!(value[0]["n"], JsonValue::RawNum("#12345678901234567890".to_string()));
+}
+
+#[test]
+fn number_handler_can_capture_state() {
+    let seen = Arc::new(AtomicUsize::new(0));
+    let scale = 10;
+    let counter = Arc::clone(&seen);
+    let options = ParseOptions {
+        number_handler: Some(Arc::new(move |lexeme| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            JsonValue::Int(lexeme.parse::<i64>().unwrap() * scale)
+        })),
+        ..ParseOptions::default()
+    };
+    // Clones share the one handler, and so its state.
+    let cloned = options.clone();
+    assert_eq!(parse_with_options("[1, 2, 3]", &options).unwrap(), parse("[10, 20, 30]").unwrap());
+    assert_eq!(parse_with_options("{\"a\": 4}", &cloned).unwrap()["a"], JsonValue::Int(40));
+    assert_eq!(seen.load(Ordering::Relaxed), 4);
+
+    let debug = format!("{:?}", options);
+    assert!(debug.contains("number_handler: Some(<fn>)"), "{}", debug);
+    assert!(format!("{:?}", ParseOptions::default()).contains("number_handler: None"));
+}
+
+#[test]
+fn unsafe_integers_can_be_rejected() {
+    // 2^53 + 1, which rounds to 2^53 as an f64.
+    assert_eq!(parse("9007199254740993").unwrap(), JsonValue::Int(9_007_199_254_740_993));
+
+    let strict = ParseOptions { enforce_js_safe_integers: true, ..ParseOptions::default() };
+    let err = parse_with_options("[1, 9007199254740993]", &strict).unwrap_err();
+    assert!(matches!(err, ParseError::UnsafeInteger { .. }), "{:?}", err);
+    assert_eq!(err.position().unwrap().offset, 4);
+    assert_eq!(err.to_string(), "integer is outside the safe JavaScript range at line 1, column 5");
+
+    for input in ["9007199254740991", "-9007199254740991", "0", "-0", "9007199254740993.0", "1e300"] {
+        assert!(parse_with_options(input, &strict).is_ok(), "{:?}", input);
+    }
+    for input in ["9007199254740992", "-9007199254740992", "123456789012345678901234567890"] {
+        assert!(matches!(parse_with_options(input, &strict), Err(ParseError::UnsafeInteger { .. })), "{:?}", input);
+    }
+}
+
+#[test]
+fn keys_can_be_case_folded() {
+    let folding = |case| ParseOptions { normalize_keys: Some(case), ..ParseOptions::default() };
+    let value = parse_with_options(r#"{"Name":1,"NAME":2}"#, &folding(KeyCase::Lower)).unwrap();
+    assert_eq!(value, parse(r#"{"name": 2}"#).unwrap());
+
+    // Nested keys are folded too, and colliding keys keep the first's place.
+    let input = r#"{"Id": 1, "Tags": [{"Kind": "a"}], "ID": 3, "x": {"Ä": true}}"#;
+    let value = parse_with_options(input, &folding(KeyCase::Upper)).unwrap();
+    assert_eq!(value.to_string(), r#"{"ID":3,"TAGS":[{"KIND":"a"}],"X":{"Ä":true}}"#);
+    let value = parse_with_options(input, &ParseOptions { intern_keys: true, ..folding(KeyCase::Lower) }).unwrap();
+    assert_eq!(value.to_string(), r#"{"id":3,"tags":[{"kind":"a"}],"x":{"ä":true}}"#);
+
+    // Only the keys as written count as duplicates.
+    let strict = ParseOptions { error_on_duplicate_keys: true, ..folding(KeyCase::Lower) };
+    assert_eq!(parse_with_options(r#"{"A": 1, "a": 2}"#, &strict).unwrap(), parse(r#"{"a": 2}"#).unwrap());
+}
+
+#[test]
+fn input_over_the_limit_is_rejected_before_parsing() {
+    let options = ParseOptions { max_input_bytes: Some(8), ..ParseOptions::default() };
+    assert!(parse_with_options("[1, 2, 3]", &options).is_err());
+    assert!(parse_with_options("[1,2,3] ", &options).is_ok());
+
+    // Nothing is parsed, so the syntax error in here goes unnoticed.
+    let err = parse_with_options("[1,\n2, oops]", &options).unwrap_err();
+    assert!(matches!(err, ParseError::InputTooLarge { limit: 8, .. }), "{:?}", err);
+    assert_eq!(err.to_string(), "input exceeds the limit of 8 bytes at line 2, column 5");
+    // The position is that of the character the limit falls in.
+    let err = parse_with_options("\"1234567北\"", &options).unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 8);
+    let err = parse_with_options("\"123456北\"", &options).unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 7);
+}
+
+#[test]
+fn lenient_escapes_accept_hex_and_nul() {
+    let lenient = ParseOptions { lenient_escapes: true, ..ParseOptions::default() };
+    let value = parse_with_options(r#"{"\x41": "\x41\x7a\0-\xff"}"#, &lenient).unwrap();
+    assert_eq!(value["A"], JsonValue::Str("Az\0-\u{ff}".to_string()));
+
+    for input in [r#""\x41""#, r#""\0""#] {
+        assert!(matches!(parse(input), Err(ParseError::InvalidEscape { .. })), "{:?}", input);
+    }
+    // Exactly two hex digits are needed.
+    assert!(matches!(parse_with_options(r#""\x4""#, &lenient), Err(ParseError::InvalidEscape { .. })));
+}
+
+// The address of each key in each object of the array `value`.
+fn key_addresses(value: &JsonValue) -> Vec<Vec<*const u8>> {
+    value.elements().map(|record| record.entries().map(|(key, _)| key.as_ptr()).collect()).collect()
+}
+
+#[test]
+fn interned_keys_share_storage() {
+    let records: Vec<String> =
+        (0..1000).map(|i| format!(r#"{{"id": {}, "name": "user {}", "tags": {{"id": 0}}}}"#, i, i)).collect();
+    let input = format!("[{}]", records.join(","));
+    let interning = ParseOptions { intern_keys: true, ..ParseOptions::default() };
+    let value = parse_with_options(&input, &interning).unwrap();
+    assert_eq!(value, parse(&input).unwrap());
+
+    let addresses = key_addresses(&value);
+    assert!(addresses.iter().all(|keys| *keys == addresses[0]));
+    // The same key in a nested object is shared too.
+    assert_eq!(value[0]["tags"].entries().next().unwrap().0.as_ptr(), addresses[0][0]);
+
+    let addresses = key_addresses(&parse(&input).unwrap());
+    assert_ne!(addresses[0], addresses[1]);
+}
+
+#[test]
+fn big_integers_keep_their_digits() {
+    let options = ParseOptions { big_integers: true, ..ParseOptions::default() };
+    let value =
+        parse_with_options("[99999999999999999999, -9223372036854775809, 9223372036854775807, 1.5, 1e30]", &options)
+            .unwrap();
+    assert_eq!(value[0], JsonValue::BigInt("99999999999999999999".to_string()));
+    assert_eq!(value[1], JsonValue::BigInt("-9223372036854775809".to_string()));
+    assert_eq!(value[2], JsonValue::Int(i64::MAX));
+    assert_eq!(value[3], JsonValue::Float(1.5));
+    assert_eq!(value[4], JsonValue::Float(1e30));
+
+    assert!(value[0].is_number());
+    assert_eq!(value[0].as_i64(), None);
+    assert_eq!(value[0].as_f64(), Some(1e20));
+    assert_eq!(value.to_string(), "[99999999999999999999,-9223372036854775809,9223372036854775807,1.5,1e30]");
+
+    // Without the option they're rounded to floats, as before.
+    assert_eq!(parse("99999999999999999999").unwrap(), JsonValue::Float(1e20));
+}
+
+#[test]
+fn big_integers_hash_by_their_digits() {
+    let options = ParseOptions { big_integers: true, ..ParseOptions::default() };
+    let a = parse_with_options("12345678901234567890123", &options).unwrap();
+    let b = parse_with_options("12345678901234567890124", &options).unwrap();
+    assert_eq!(a, JsonValue::BigInt("12345678901234567890123".to_string()));
+    assert_ne!(a.content_hash(), b.content_hash());
+    assert_eq!(a.to_canonical_string(), "12345678901234567890123");
+    assert_eq!(b.to_canonical_string(), "12345678901234567890124");
+
+    let value = parse_with_options(r#"{"n": -99999999999999999999, "m": [18446744073709551616]}"#, &options).unwrap();
+    assert_eq!(value.to_canonical_string(), r#"{"m":[18446744073709551616],"n":-99999999999999999999}"#);
+}
+
+#[test]
+fn duplicate_keys_can_be_errors() {
+    let strict = ParseOptions { error_on_duplicate_keys: true, ..ParseOptions::default() };
+    match parse_with_options(r#"{"a":1,"a":2}"#, &strict) {
+        Err(ParseError::DuplicateKey { key, position }) => assert_eq!((key.as_str(), position.offset), ("a", 7)),
+        other => panic!("expected DuplicateKey, got {:?}", other),
+    }
+
+    // Keys are compared decoded, and only within the same object.
+    let err = parse_with_options(r#"[{"k": {"k": 1}}, {"x": 1, "x": 2}]"#, &strict).unwrap_err();
+    assert_eq!(err.to_string(), "duplicate key \"x\" at line 1, column 28");
+    assert!(parse_with_options(r#"{"a": {"a": 1}, "b": [{"a": 2}, {"a": 3}]}"#, &strict).is_ok());
+    assert!(matches!(
+        parse_with_options(r#"{"a": 1, "\u0061": 2}"#, &strict),
+        Err(ParseError::DuplicateKey { key, .. }) if key == "a"
+    ));
+
+    assert_eq!(parse(r#"{"a":1,"a":2}"#).unwrap()["a"], JsonValue::Int(2));
+}
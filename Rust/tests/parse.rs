@@ -0,0 +1,381 @@
+use json_parser::{parse, JsonValue, ParseError};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+fn float(input: &str) -> f64 {
+    match parse(input) {
+        Ok(JsonValue::Float(n)) => n,
+        other => panic!("expected a float for {:?}, got {:?}", input, other),
+    }
+}
+
+fn int(input: &str) -> i64 {
+    match parse(input) {
+        Ok(JsonValue::Int(n)) => n,
+        other => panic!("expected an integer for {:?}, got {:?}", input, other),
+    }
+}
+
+fn string(input: &str) -> String {
+    match parse(input) {
+        Ok(JsonValue::Str(s)) => s,
+        other => panic!("expected a string for {:?}, got {:?}", input, other),
+    }
+}
+
+#[test]
+fn parses_sample_document() {
+    let JsonValue::Object(root) = parse(SAMPLE).unwrap() else {
+        panic!("root should be an object");
+    };
+    assert!(matches!(&root["nickname"], JsonValue::Str(s) if s == "张三"));
+    assert!(matches!(root["age"], JsonValue::Int(30)));
+    assert!(matches!(root["is_teacher"], JsonValue::Bool(false)));
+    assert!(matches!(&root["scores"], JsonValue::Array(items) if items.len() == 3));
+
+    let JsonValue::Object(address) = &root["address"] else {
+        panic!("address should be an object");
+    };
+    assert!(matches!(&address["city"], JsonValue::Str(s) if s == "北京"));
+    assert!(matches!(&address["code"], JsonValue::Array(items) if items.len() == 2));
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn parses_numbers() {
+    assert_eq!(int("0"), 0);
+    assert!(float("-0").is_sign_negative());
+    assert_eq!(int("42"), 42);
+    assert_eq!(float("3.14"), 3.14);
+    assert_eq!(float("-2.5E-3"), -2.5e-3);
+    assert_eq!(float("1e10"), 1e10);
+}
+
+#[test]
+fn keeps_integers_exact() {
+    assert_eq!(int("9007199254740993"), 9_007_199_254_740_993);
+    assert_eq!(int("-9223372036854775808"), i64::MIN);
+    assert_eq!(int("9223372036854775807"), i64::MAX);
+    assert_eq!(int("-17"), -17);
+    assert_eq!(parse("9007199254740993").unwrap().to_string(), "9007199254740993");
+}
+
+#[test]
+fn falls_back_to_float_outside_i64() {
+    assert_eq!(float("9223372036854775808"), 2f64.powi(63));
+    assert_eq!(float("-1.5"), -1.5);
+    assert_eq!(float("2E3"), 2000.0);
+}
+
+#[test]
+fn coerces_numbers() {
+    let i = parse("7").unwrap();
+    assert_eq!(i.as_i64(), Some(7));
+    assert_eq!(i.as_f64(), Some(7.0));
+
+    let f = parse("7.0").unwrap();
+    assert_eq!(f.as_i64(), Some(7));
+    assert_eq!(f.as_f64(), Some(7.0));
+
+    assert_eq!(parse("7.5").unwrap().as_i64(), None);
+    assert_eq!(parse("1e300").unwrap().as_i64(), None);
+    assert_eq!(parse("\"7\"").unwrap().as_i64(), None);
+    assert_eq!(parse("null").unwrap().as_f64(), None);
+}
+
+#[test]
+fn rejects_incomplete_numbers() {
+    for input in [".5", "1.", "1e", "1e+", "-"] {
+        assert!(parse(input).is_err(), "{:?} should be rejected", input);
+    }
+}
+
+#[test]
+fn nested_objects_do_not_panic() {
+    let depth = 64;
+    let input = format!("{}{{}}{}", r#"{"a":"#.repeat(depth), "}".repeat(depth));
+    let mut value = &parse(&input).unwrap();
+    let mut levels = 0;
+    while let JsonValue::Object(map) = value {
+        levels += 1;
+        match map.get("a") {
+            Some(child) => value = child,
+            None => break,
+        }
+    }
+    assert_eq!(levels, depth + 1);
+
+    assert!(matches!(parse("{1: 2}"), Err(ParseError::UnexpectedChar { found: '1', .. })));
+    assert!(matches!(parse(r#"{"a": {null: 1}}"#), Err(ParseError::UnquotedKey { .. })));
+}
+
+#[test]
+fn decodes_escapes() {
+    assert_eq!(string(r#""he said \"hi\"""#), "he said \"hi\"");
+    assert_eq!(string(r#""C:\\temp\\""#), "C:\\temp\\");
+    assert_eq!(string(r#""a\/b\b\f\n\r\t""#), "a/b\u{08}\u{0C}\n\r\t");
+    assert_eq!(string(r#""line1\nline2\t\"quoted\"""#), "line1\nline2\t\"quoted\"");
+}
+
+#[test]
+fn rejects_bad_escapes() {
+    assert!(parse(r#""trailing\"#).is_err());
+    assert!(parse(r#""\q""#).is_err());
+}
+
+#[test]
+fn decodes_unicode_escapes() {
+    assert_eq!(string(r#""\u0041""#), "A");
+    assert_eq!(string(r#""\uD83D\uDE00""#), "😀");
+    assert_eq!(string(r#""\u5317\u4eac""#), "北京");
+}
+
+#[test]
+fn rejects_unpaired_surrogates() {
+    assert!(parse(r#""\uD83D""#).is_err());
+    assert!(parse(r#""\uD83Dx""#).is_err());
+    assert!(parse(r#""\uD83D\u0041""#).is_err());
+    assert!(parse(r#""\uDE00""#).is_err());
+}
+
+#[test]
+fn rejects_trailing_input() {
+    for input in [r#"{"a": 1} oops"#, "true false", "[1,2]extra"] {
+        assert!(
+            matches!(parse(input), Err(ParseError::TrailingData { .. })),
+            "{:?} should be rejected as trailing data",
+            input
+        );
+    }
+    assert_eq!(parse("[1,2]extra").unwrap_err().position().unwrap().offset, 5);
+    assert!(parse("  [1, 2]  \n").is_ok());
+}
+
+#[test]
+fn duplicate_keys_keep_the_last_value() {
+    let value = parse(r#"{"a":1,"a":2}"#).unwrap();
+    assert_eq!(value["a"], JsonValue::Int(2));
+    assert_eq!(value.as_object().unwrap().len(), 1);
+
+    let value = parse(r#"{"a":1,"b":true,"a":{"x":null},"a":"last"}"#).unwrap();
+    assert_eq!(value["a"], JsonValue::Str("last".to_string()));
+    assert_eq!(value["b"], JsonValue::Bool(true));
+}
+
+#[test]
+fn rejects_raw_control_characters_in_strings() {
+    for input in ["\"a\tb\"", "\"line\nbreak\"", "\"nul\u{0}\"", "[\"ok\", \"\u{1f}\"]"] {
+        assert!(parse(input).is_err(), "{:?} should be rejected", input);
+    }
+    let err = parse("{\"key\": \"tab\there\"}").unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 12);
+
+    assert_eq!(string(r#""a\tb\nc""#), "a\tb\nc");
+    assert_eq!(string("\"\u{7f} is not a control character in JSON\""), "\u{7f} is not a control character in JSON");
+}
+
+#[test]
+fn empty_containers_may_hold_whitespace() {
+    for input in ["{}", "{ }", "{\n\t}", " { \r\n } "] {
+        assert_eq!(parse(input).unwrap(), JsonValue::Object(Default::default()), "{:?}", input);
+    }
+    for input in ["[]", "[ ]", "[\n\t]"] {
+        assert_eq!(parse(input).unwrap(), JsonValue::Array(vec![]), "{:?}", input);
+    }
+    let populated = parse(r#"{"a":{},"b":[],"c":1}"#).unwrap();
+    assert_eq!(parse("{ \"a\" : { } , \"b\" : [\n] , \"c\" : 1 }").unwrap(), populated);
+
+    // Whitespace inside the brackets doesn't move where errors are reported.
+    let offset = |input: &str| parse(input).unwrap_err().position().unwrap().offset;
+    assert_eq!(offset("{ , }"), 2);
+    assert_eq!(offset("[  , ]"), 3);
+    assert_eq!(offset("{ \"a\" }"), 6);
+    assert_eq!(offset("[1 ,  ]"), 6);
+    assert_eq!(offset("{ \"a\": 1  "), 10);
+}
+
+#[test]
+fn allows_whitespace_throughout_arrays() {
+    assert_eq!(parse("[  ]").unwrap(), JsonValue::Array(vec![]));
+    assert_eq!(parse("[\n]").unwrap(), JsonValue::Array(vec![]));
+    assert_eq!(parse("[ 1 , 2 ]").unwrap(), parse("[1,2]").unwrap());
+    assert_eq!(parse("[\n  1,\n  2\n]").unwrap(), parse("[1,2]").unwrap());
+    assert_eq!(parse("[\t[ ] ,\r\n{ } ]").unwrap(), parse("[[],{}]").unwrap());
+    assert!(parse("[ 1 2 ]").is_err());
+    assert!(parse("[ , ]").is_err());
+}
+
+#[test]
+fn parses_large_arrays_whatever_follows_them() {
+    let items: Vec<String> = (0..5000).map(|i| (i % 1000).to_string()).collect();
+    let expected: Vec<JsonValue> = (0..5000).map(|i| JsonValue::Int(i % 1000)).collect();
+    let array = format!("[{}]", items.join(","));
+    assert_eq!(parse(&array).unwrap(), JsonValue::Array(expected.clone()));
+
+    // Room is reserved as if the array ran to the end of the input, and
+    // given back when it doesn't.
+    let input = format!("{{\"items\": {}, \"padding\": \"{}\"}}", array, "x".repeat(1 << 20));
+    let value = parse(&input).unwrap();
+    let parsed = value["items"].as_array().unwrap();
+    assert_eq!(*parsed, expected);
+    assert!(parsed.capacity() <= 2 * parsed.len());
+}
+
+#[test]
+fn skips_a_leading_byte_order_mark() {
+    assert_eq!(parse("\u{FEFF}{\"a\":1}").unwrap(), parse("{\"a\":1}").unwrap());
+    assert_eq!(json_parser::parse_bytes(b"\xEF\xBB\xBF[1]").unwrap(), parse("[1]").unwrap());
+}
+
+#[test]
+fn rejects_a_byte_order_mark_elsewhere() {
+    assert!(matches!(parse("{\"a\":\u{FEFF}1}"), Err(ParseError::UnexpectedChar { found: '\u{FEFF}', .. })));
+    assert!(matches!(parse(" \u{FEFF}[1]"), Err(ParseError::UnexpectedChar { found: '\u{FEFF}', .. })));
+    assert!(matches!(parse("[1]\u{FEFF}"), Err(ParseError::TrailingData { .. })));
+    assert!(parse("\u{FEFF}\u{FEFF}[1]").is_err());
+
+    // Streams and NDJSON only allow one at the very start of the input, not
+    // before each value or line.
+    let values: Vec<_> = json_parser::parse_stream("\u{FEFF}1 2").map(Result::unwrap).collect();
+    assert_eq!(values, [JsonValue::Int(1), JsonValue::Int(2)]);
+    let values: Vec<_> = json_parser::parse_stream("1\u{FEFF}2").collect();
+    assert_eq!(values.len(), 2);
+    let err = values[1].as_ref().unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: '\u{FEFF}', .. }), "{:?}", err);
+    assert_eq!(err.position().unwrap().offset, 1);
+
+    let lines: Vec<_> = json_parser::parse_lines("\u{FEFF}[1]\n\u{FEFF}[2]\n").collect();
+    assert_eq!(lines[0].as_ref().unwrap(), &parse("[1]").unwrap());
+    let err = lines[1].as_ref().unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: '\u{FEFF}', .. }), "{:?}", err);
+    assert_eq!((err.position().unwrap().line, err.position().unwrap().column), (2, 1));
+
+    assert_eq!(json_parser::parse_fragment("\u{FEFF}[1] 2").unwrap(), (parse("[1]").unwrap(), " 2"));
+}
+
+#[test]
+fn follows_the_number_grammar_strictly() {
+    let accepted = [
+        ("0", JsonValue::Int(0)),
+        ("-0", JsonValue::Float(-0.0)),
+        ("10", JsonValue::Int(10)),
+        ("-10", JsonValue::Int(-10)),
+        ("0.5", JsonValue::Float(0.5)),
+        ("-0.5e1", JsonValue::Float(-5.0)),
+        ("0e0", JsonValue::Float(0.0)),
+        ("100", JsonValue::Int(100)),
+    ];
+    for (input, expected) in accepted {
+        assert_eq!(parse(input).unwrap(), expected, "{:?}", input);
+    }
+
+    let rejected = ["01", "-01", "00", "007", "+5", "-", "--1", "- 1", "0x1F", "1.e5", ".1", "[01]"];
+    for input in rejected {
+        assert!(parse(input).is_err(), "{:?} should be rejected", input);
+    }
+    assert_eq!(parse("[01]").unwrap_err().position().unwrap().offset, 2);
+}
+
+#[test]
+fn parse_fragment_returns_the_remainder() {
+    let (value, rest) = json_parser::parse_fragment("[1,2]REST").unwrap();
+    assert_eq!(value, parse("[1,2]").unwrap());
+    assert_eq!(rest, "REST");
+
+    let (value, rest) = json_parser::parse_fragment("  {\"a\": true} \n{\"b\": null}").unwrap();
+    assert_eq!(value["a"], JsonValue::Bool(true));
+    assert_eq!(rest, " \n{\"b\": null}");
+    let (value, rest) = json_parser::parse_fragment(rest).unwrap();
+    assert_eq!(value["b"], JsonValue::Null);
+    assert_eq!(rest, "");
+
+    assert_eq!(json_parser::parse_fragment("12,13").unwrap(), (JsonValue::Int(12), ",13"));
+    assert!(matches!(json_parser::parse_fragment("REST"), Err(ParseError::UnexpectedChar { found: 'R', .. })));
+    assert!(json_parser::parse_fragment("").is_err());
+}
+
+#[test]
+fn implements_from_str() {
+    let value = r#"{"a": [1, 2]}"#.parse::<JsonValue>().unwrap();
+    assert_eq!(value, parse(r#"{"a": [1, 2]}"#).unwrap());
+    let value: JsonValue = "  null ".parse().unwrap();
+    assert_eq!(value, JsonValue::Null);
+
+    assert!(matches!("[1, 2".parse::<JsonValue>(), Err(ParseError::UnexpectedEof { .. })));
+    assert!(matches!("1 2".parse::<JsonValue>(), Err(ParseError::TrailingData { .. })));
+}
+
+#[test]
+fn decodes_keys_with_special_characters() {
+    let value = parse(r#"{"": 1, "a\"b": 2, "北京": 3, "tab\there": 4, "\u5357": 5}"#).unwrap();
+    assert_eq!(value[""], JsonValue::Int(1));
+    assert_eq!(value["a\"b"], JsonValue::Int(2));
+    assert_eq!(value["北京"], JsonValue::Int(3));
+    assert_eq!(value["tab\there"], JsonValue::Int(4));
+    assert_eq!(value["南"], JsonValue::Int(5));
+    assert_eq!(value.as_object().unwrap().len(), 5);
+    assert!(value.get("a\\\"b").is_none());
+
+    // Keys are written back with the same escaping as strings.
+    assert_eq!(parse(r#"{"a\"b": 2}"#).unwrap().to_string(), r#"{"a\"b":2}"#);
+    assert_eq!(parse(r#"{"": {"": []}}"#).unwrap().to_string(), r#"{"":{"":[]}}"#);
+}
+
+#[test]
+fn parses_each_line_separately() {
+    let input = "{\"id\": 1, \"msg\": \"start\"}\n\n{\"id\": 2, \"msg\": }\r\n  \n[3]\n";
+    let results: Vec<_> = json_parser::parse_lines(input).collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &parse(r#"{"id": 1, "msg": "start"}"#).unwrap());
+    assert_eq!(results[2].as_ref().unwrap(), &parse("[3]").unwrap());
+
+    let position = results[1].as_ref().unwrap_err().position().unwrap();
+    assert_eq!((position.line, position.column), (3, 18));
+    assert_eq!(&input[position.offset..position.offset + 1], "}");
+}
+
+#[test]
+fn allows_any_whitespace_around_keys_and_colons() {
+    let expected = parse(r#"{"a":1,"b":[2]}"#).unwrap();
+    for input in [
+        "{ \"a\" : 1 , \"b\" : [2] }",
+        "{\"a\"\n:\n1,\"b\"\n:\n[2]}",
+        "{\t\"a\"\t:\t1,\r\n\"b\"\t\r\n:  \t[2]\n}",
+        "\n{\n\n  \"a\"   \n\n  :\n\n  1\n,\n  \"b\":\t[ 2 ]\n}\n",
+    ] {
+        assert_eq!(parse(input).unwrap(), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn parses_concatenated_values_in_order() {
+    let values: Vec<_> = json_parser::parse_stream(r#"{"a":1}[2,3]true"#).map(Result::unwrap).collect();
+    assert_eq!(values, [parse(r#"{"a":1}"#).unwrap(), parse("[2,3]").unwrap(), JsonValue::Bool(true)]);
+
+    let values: Vec<_> = json_parser::parse_stream(" 1\n\"s\"null  ").map(Result::unwrap).collect();
+    assert_eq!(values, [JsonValue::Int(1), JsonValue::Str("s".to_string()), JsonValue::Null]);
+    assert_eq!(json_parser::parse_stream(" \n ").count(), 0);
+}
+
+#[test]
+fn stream_errors_end_the_iteration() {
+    let mut values = json_parser::parse_stream("[1]\n{\"a\" 2} [3]");
+    assert_eq!(values.next().unwrap().unwrap(), parse("[1]").unwrap());
+    let err = values.next().unwrap().unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: '2', .. }), "{:?}", err);
+    let position = err.position().unwrap();
+    assert_eq!((position.offset, position.line, position.column), (9, 2, 6));
+    assert!(values.next().is_none());
+}
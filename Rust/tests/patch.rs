@@ -0,0 +1,106 @@
+use json_parser::{json, JsonValue, PatchError};
+
+fn patched(mut doc: JsonValue, patch: JsonValue) -> Result<JsonValue, PatchError> {
+    doc.apply_patch(&patch).map(|()| doc)
+}
+
+#[test]
+fn adds_members_and_elements() {
+    let doc = json!({"a": {"b": 1}, "list": [1, 3]});
+    let patch = json!([
+        {"op": "add", "path": "/a/c", "value": [true]},
+        {"op": "add", "path": "/list/1", "value": 2},
+        {"op": "add", "path": "/list/-", "value": 4},
+        {"op": "add", "path": "/a/b", "value": "replaced"}
+    ]);
+    assert_eq!(patched(doc, patch), Ok(json!({"a": {"b": "replaced", "c": [true]}, "list": [1, 2, 3, 4]})));
+    assert_eq!(patched(json!({"a": 1}), json!([{"op": "add", "path": "", "value": [1]}])), Ok(json!([1])));
+}
+
+#[test]
+fn removes_members_and_elements() {
+    let doc = json!({"a": 1, "b": [1, 2, 3]});
+    let patch = json!([{"op": "remove", "path": "/a"}, {"op": "remove", "path": "/b/0"}]);
+    assert_eq!(patched(doc, patch), Ok(json!({"b": [2, 3]})));
+}
+
+#[test]
+fn replaces_existing_values() {
+    let doc = json!({"a": {"b": [0, 1]}});
+    let patch = json!([
+        {"op": "replace", "path": "/a/b/1", "value": "one"},
+        {"op": "replace", "path": "", "value": {"root": null}}
+    ]);
+    assert_eq!(patched(doc.clone(), patch), Ok(json!({"root": null})));
+    let missing = json!([{"op": "replace", "path": "/a/c", "value": 1}]);
+    assert_eq!(patched(doc, missing), Err(PatchError::InvalidPath { index: 0, path: "/a/c".to_string() }));
+}
+
+#[test]
+fn moves_values() {
+    let doc = json!({"a": {"x": 1}, "b": [1, 2]});
+    let patch = json!([
+        {"op": "move", "from": "/a/x", "path": "/b/0"},
+        {"op": "move", "from": "/b", "path": "/c"}
+    ]);
+    assert_eq!(patched(doc.clone(), patch), Ok(json!({"a": {}, "c": [1, 1, 2]})));
+
+    let into_child = json!([{"op": "move", "from": "/a", "path": "/a/x/y"}]);
+    assert!(matches!(patched(doc, into_child), Err(PatchError::InvalidPath { index: 0, .. })));
+}
+
+#[test]
+fn copies_values() {
+    let doc = json!({"a": {"x": [1]}});
+    let patch = json!([{"op": "copy", "from": "/a/x", "path": "/b"}, {"op": "add", "path": "/b/-", "value": 2}]);
+    assert_eq!(patched(doc, patch), Ok(json!({"a": {"x": [1]}, "b": [1, 2]})));
+}
+
+#[test]
+fn tests_values() {
+    let doc = json!({"a": [1.0, "s", {"k": null}], "a~b": true});
+    let patch = json!([
+        {"op": "test", "path": "/a", "value": [1, "s", {"k": null}]},
+        {"op": "test", "path": "/a~0b", "value": true}
+    ]);
+    assert_eq!(patched(doc.clone(), patch), Ok(doc));
+}
+
+#[test]
+fn failed_operations_leave_the_document_unchanged() {
+    let original = json!({"a": 1, "b": [1]});
+    let mut doc = original.clone();
+    let patch = json!([
+        {"op": "add", "path": "/c", "value": 3},
+        {"op": "remove", "path": "/b/0"},
+        {"op": "test", "path": "/a", "value": 2}
+    ]);
+    let err = doc.apply_patch(&patch).unwrap_err();
+    assert_eq!(err, PatchError::TestFailed { index: 2, path: "/a".to_string() });
+    assert_eq!(err.to_string(), "test operation 2 failed at \"/a\"");
+    assert_eq!(doc, original);
+
+    let out_of_bounds = json!([{"op": "add", "path": "/b/5", "value": 0}]);
+    assert!(matches!(doc.apply_patch(&out_of_bounds), Err(PatchError::InvalidPath { .. })));
+    assert_eq!(doc, original);
+}
+
+#[test]
+fn rejects_malformed_patches() {
+    let mut doc = json!({});
+    assert_eq!(doc.apply_patch(&json!({"op": "add"})), Err(PatchError::NotAnArray));
+    for operation in [
+        json!({"op": "frobnicate", "path": "/a"}),
+        json!({"path": "/a", "value": 1}),
+        json!({"op": "add", "path": "/a"}),
+        json!({"op": "move", "path": "/a"}),
+        json!({"op": "add", "path": 1, "value": 1}),
+        json!("add"),
+    ] {
+        let patch = JsonValue::Array(vec![operation]);
+        assert_eq!(doc.apply_patch(&patch), Err(PatchError::InvalidOperation { index: 0 }), "{}", patch);
+    }
+    let patch = json!([{"op": "test", "path": "", "value": {}}, {"op": "remove"}]);
+    assert_eq!(doc.apply_patch(&patch), Err(PatchError::InvalidOperation { index: 1 }));
+    assert!(matches!(doc.apply_patch(&json!([{"op": "remove", "path": "a"}])), Err(PatchError::InvalidPath { .. })));
+}
@@ -0,0 +1,126 @@
+use json_parser::{json, parse, JsonValue, PointerError};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+#[test]
+fn looks_up_nested_members() {
+    let doc = parse(SAMPLE).unwrap();
+    assert_eq!(doc.pointer(""), Some(&doc));
+    assert_eq!(doc.pointer("/address/city"), Some(&json!("北京")));
+    assert_eq!(doc.pointer("/age"), Some(&json!(30)));
+    assert_eq!(doc.pointer("/address/missing"), None);
+}
+
+#[test]
+fn indexes_into_arrays() {
+    let doc = parse(SAMPLE).unwrap();
+    assert_eq!(doc.pointer("/scores/0"), Some(&json!(90)));
+    assert_eq!(doc.pointer("/address/code/1"), Some(&json!(2000)));
+    assert_eq!(doc.pointer("/scores/3"), None);
+    assert_eq!(doc.pointer("/scores/-"), None);
+    assert_eq!(doc.pointer("/scores/01"), None);
+    assert_eq!(doc.pointer("/scores/+1"), None);
+}
+
+#[test]
+fn stepping_into_the_wrong_type_is_none() {
+    let doc = parse(SAMPLE).unwrap();
+    assert_eq!(doc.pointer("/age/0"), None);
+    assert_eq!(doc.pointer("/nickname/x"), None);
+    assert_eq!(doc.pointer("/scores/first"), None);
+    assert_eq!(doc.pointer("address"), None);
+}
+
+#[test]
+fn decodes_escaped_tokens() {
+    let doc = parse(r#"{"a/b": 1, "m~n": 2, "~1": 3, "": {"": 4}}"#).unwrap();
+    assert_eq!(doc.pointer("/a~1b"), Some(&json!(1)));
+    assert_eq!(doc.pointer("/m~0n"), Some(&json!(2)));
+    assert_eq!(doc.pointer("/~01"), Some(&json!(3)));
+    assert_eq!(doc.pointer("//"), Some(&json!(4)));
+}
+
+#[test]
+fn pointer_mut_allows_in_place_edits() {
+    let mut doc = parse(SAMPLE).unwrap();
+    *doc.pointer_mut("/address/city").unwrap() = JsonValue::from("上海");
+    assert_eq!(doc["address"]["city"], json!("上海"));
+    assert!(doc.pointer_mut("/address/zip").is_none());
+}
+
+#[test]
+fn sets_existing_values() {
+    let mut doc = parse(SAMPLE).unwrap();
+    doc.set_pointer("/address/city", json!("上海")).unwrap();
+    doc.set_pointer("/address/code/1", json!(3000)).unwrap();
+    doc.set_pointer("/scores/-", json!(70)).unwrap();
+    doc.set_pointer("/address/street", json!("南京路")).unwrap();
+    assert_eq!(doc.pointer("/address/city"), Some(&json!("上海")));
+    assert_eq!(doc["address"]["code"], json!([200, 3000]));
+    assert_eq!(doc["scores"], json!([90, 85, 95, 70]));
+    assert_eq!(doc["address"]["street"], json!("南京路"));
+
+    doc.set_pointer("", json!(null)).unwrap();
+    assert_eq!(doc, JsonValue::Null);
+}
+
+#[test]
+fn creates_missing_objects_when_asked() {
+    let mut doc = parse(SAMPLE).unwrap();
+    let not_found = PointerError::NotFound { path: "/meta".to_string() };
+    assert_eq!(doc.set_pointer("/meta/tags/a~1b", json!(1)), Err(not_found));
+
+    doc.set_pointer_creating("/meta/tags/a~1b", json!(1)).unwrap();
+    assert_eq!(doc["meta"], json!({"tags": {"a/b": 1}}));
+    assert_eq!(doc["nickname"], json!("张三"));
+}
+
+#[test]
+fn refuses_to_step_into_the_wrong_type() {
+    let mut doc = parse(SAMPLE).unwrap();
+    let before = doc.clone();
+    let mismatch = |path: &str| Err(PointerError::TypeMismatch { path: path.to_string() });
+    assert_eq!(doc.set_pointer_creating("/age/years", json!(1)), mismatch("/age/years"));
+    assert_eq!(doc.set_pointer_creating("/scores/first", json!(1)), mismatch("/scores/first"));
+    assert_eq!(doc.set_pointer("/scores/3", json!(1)), Err(PointerError::NotFound { path: "/scores/3".to_string() }));
+    assert_eq!(doc.set_pointer("age", json!(1)), Err(PointerError::Malformed));
+    assert_eq!(doc, before);
+}
+
+#[test]
+fn removes_values() {
+    let mut doc = parse(SAMPLE).unwrap();
+    assert_eq!(doc.remove_pointer("/scores/0"), Some(json!(90)));
+    assert_eq!(doc["scores"], json!([85, 95]));
+    assert_eq!(doc.pointer("/scores/0"), Some(&json!(85)));
+    assert_eq!(doc.remove_pointer("/address/city"), Some(json!("北京")));
+    assert_eq!(doc["address"], json!({"code": [200, 2000]}));
+
+    for ptr in ["/scores/2", "/scores/-", "/address/city", "/age/0", "/missing/x", "", "scores"] {
+        assert_eq!(doc.remove_pointer(ptr), None, "{:?}", ptr);
+    }
+    assert_eq!(doc.get("age"), Some(&json!(30)));
+}
+
+#[test]
+fn checks_whether_paths_exist() {
+    let doc = parse(SAMPLE).unwrap();
+    for ptr in ["", "/address/city", "/address/code/1", "/scores/0"] {
+        assert!(doc.contains_pointer(ptr), "{:?}", ptr);
+    }
+    for ptr in ["/address/zip", "/missing/city", "/address/code/2", "/scores/01", "/age/0", "address"] {
+        assert!(!doc.contains_pointer(ptr), "{:?}", ptr);
+    }
+    // A null value is still there.
+    assert!(json!({"a": null}).contains_pointer("/a"));
+}
@@ -0,0 +1,62 @@
+use json_parser::{json, parse, JsonValue};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+fn ints(values: Vec<&JsonValue>) -> Vec<i64> {
+    values.into_iter().map(|v| v.as_i64().unwrap()).collect()
+}
+
+#[test]
+fn follows_dotted_paths() {
+    let sample = parse(SAMPLE).unwrap();
+    assert_eq!(sample.query("$.address.city"), [&json!("北京")]);
+    assert_eq!(sample.query("$['address']['street']"), [&json!("中关村大街")]);
+    assert_eq!(sample.query("$"), [&sample]);
+    assert!(sample.query("$.address.zip").is_empty());
+}
+
+#[test]
+fn indexes_arrays() {
+    let sample = parse(SAMPLE).unwrap();
+    assert_eq!(ints(sample.query("$.scores[1]")), [85]);
+    assert_eq!(ints(sample.query("$.address.code[0]")), [200]);
+    assert!(sample.query("$.scores[3]").is_empty());
+    assert!(sample.query("$.address[0]").is_empty());
+}
+
+#[test]
+fn expands_wildcards() {
+    let sample = parse(SAMPLE).unwrap();
+    assert_eq!(ints(sample.query("$.scores[*]")), [90, 85, 95]);
+    assert_eq!(sample.query("$.address.*").len(), 3);
+    assert_eq!(ints(sample.query("$.address.code.*")), [200, 2000]);
+}
+
+#[test]
+fn descends_recursively() {
+    let doc = json!({"a": {"id": 1, "b": [{"id": 2}, {"c": {"id": 3}}]}, "id": 0});
+    // The root's own match comes before those nested inside it.
+    assert_eq!(ints(doc.query("$..id")), [0, 1, 2, 3]);
+    assert_eq!(ints(doc.query("$.a..id")), [1, 2, 3]);
+    assert_eq!(doc.query("$..b[1]"), [&json!({"c": {"id": 3}})]);
+}
+
+#[test]
+fn malformed_paths_match_nothing() {
+    let sample = parse(SAMPLE).unwrap();
+    for path in ["", "address", "$.", "$[", "$.scores[-1]", "$.scores[x]", "$..", "$ .age"] {
+        assert!(sample.query(path).is_empty(), "{:?}", path);
+    }
+}
@@ -0,0 +1,41 @@
+use std::io::{self, Cursor, Read};
+
+use json_parser::{parse, parse_reader, ParseError};
+
+#[test]
+fn parses_from_a_cursor() {
+    let input = r#"{"city": "北京", "code": [200, 2000]}"#;
+    let value = parse_reader(Cursor::new(input.as_bytes())).unwrap();
+    assert_eq!(value, parse(input).unwrap());
+}
+
+#[test]
+fn reports_syntax_errors_with_positions() {
+    let err = parse_reader(Cursor::new(b"[1,\n 2,\n x]")).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedChar { found: 'x', .. }));
+    assert_eq!(err.position().unwrap().line, 3);
+}
+
+#[test]
+fn rejects_invalid_utf8() {
+    let err = parse_reader(Cursor::new(b"[\"ok\",\n \"bad \xE4\xB8\"]")).unwrap_err();
+    let ParseError::InvalidUtf8 { position } = err else {
+        panic!("expected InvalidUtf8, got {:?}", err);
+    };
+    assert_eq!((position.offset, position.line, position.column), (13, 2, 7));
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk on fire"))
+    }
+}
+
+#[test]
+fn surfaces_io_errors() {
+    let err = parse_reader(FailingReader).unwrap_err();
+    assert!(matches!(&err, ParseError::Io(e) if e.to_string() == "disk on fire"));
+    assert!(err.position().is_none());
+}
@@ -0,0 +1,64 @@
+use json_parser::{json, parse, parse_recovering, repair, ParseError};
+
+fn offsets(errors: &[ParseError]) -> Vec<usize> {
+    errors.iter().map(|e| e.position().unwrap().offset).collect()
+}
+
+#[test]
+fn reports_every_recoverable_error() {
+    let input = "{\n  \"a\": 1\n  \"b\": [1, 2,],\n  \"c\": true\n}";
+    let (value, errors) = parse_recovering(input);
+    assert_eq!(value, Some(json!({"a": 1, "b": [1, 2], "c": true})));
+    assert_eq!(offsets(&errors), [input.find("\"b\"").unwrap(), input.find("]").unwrap()]);
+    assert!(matches!(errors[0], ParseError::UnexpectedChar { found: '"', .. }));
+    assert!(matches!(errors[1], ParseError::UnexpectedChar { found: ']', .. }));
+    let position = errors[0].position().unwrap();
+    assert_eq!((position.line, position.column), (3, 3));
+}
+
+#[test]
+fn valid_input_has_no_errors() {
+    let input = r#"{"a": [1, 2], "b": null}"#;
+    let (value, errors) = parse_recovering(input);
+    assert_eq!(value, Some(parse(input).unwrap()));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn gives_up_on_other_errors() {
+    let (value, errors) = parse_recovering("[1 2, {\"a\" 3}]");
+    assert_eq!(value, None);
+    assert_eq!(offsets(&errors)[0], 3);
+    assert!(errors.len() >= 2);
+
+    let (value, errors) = parse_recovering("[1,");
+    assert_eq!(value, None);
+    assert!(matches!(errors[..], [ParseError::UnexpectedEof { .. }]));
+}
+
+#[test]
+fn repair_closes_what_was_left_open() {
+    assert_eq!(repair(r#"{"a":1,"#).unwrap(), json!({"a": 1}));
+    assert_eq!(repair("[1, [2, {\"b\": \"]}\"").unwrap(), json!([1, [2, {"b": "]}"}]]));
+    assert_eq!(repair("[").unwrap(), json!([]));
+}
+
+#[test]
+fn repair_drops_trailing_commas_and_accepts_bare_keys() {
+    assert_eq!(repair("[1,2,]").unwrap(), json!([1, 2]));
+    assert_eq!(repair("{name: \"张三\", tags: [\"a\",],}").unwrap(), json!({"name": "张三", "tags": ["a"]}));
+    let input = r#"{"a": [1, 2], "b": null}"#;
+    assert_eq!(repair(input).unwrap(), parse(input).unwrap());
+}
+
+#[test]
+fn repair_gives_up_on_anything_else() {
+    // Cut off inside a string, or after a key, closing brackets won't help.
+    for input in [r#"{"a": "unterminated"#, r#"{"a""#, r#"{"a":"#, ""] {
+        let err = repair(input).unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedEof { .. }), "{:?}: {:?}", input, err);
+        assert_eq!(err.position().unwrap().offset, input.len());
+    }
+    assert!(matches!(repair("[1 2]"), Err(ParseError::UnexpectedChar { found: '2', .. })));
+    assert!(matches!(repair("[1]]"), Err(ParseError::TrailingData { .. })));
+}
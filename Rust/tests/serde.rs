@@ -0,0 +1,55 @@
+#![cfg(feature = "serde")]
+
+use json_parser::{json, parse, parse_with_options, JsonValue, ParseOptions};
+
+const SAMPLE: &str = r#"{"nickname": "张三", "age": 30, "ratio": -2.5, "is_teacher": false, "spouse": null,
+    "scores": [90, 85, 95], "address": {"city": "北京", "code": [200, 2000]}, "note": "tab\there \"q\""}"#;
+
+#[test]
+fn round_trips_through_serde_json() {
+    let value = parse(SAMPLE).unwrap();
+    let written = serde_json::to_string(&value).unwrap();
+    assert_eq!(written, value.to_string());
+    assert_eq!(serde_json::from_str::<JsonValue>(&written).unwrap(), value);
+    assert_eq!(serde_json::from_str::<JsonValue>(SAMPLE).unwrap(), value);
+
+    // And through serde_json's own tree.
+    let tree = serde_json::to_value(&value).unwrap();
+    assert_eq!(tree["address"]["code"][1], serde_json::json!(2000));
+    assert_eq!(serde_json::from_value::<JsonValue>(tree).unwrap(), value);
+}
+
+#[test]
+fn keeps_member_order() {
+    let value = parse(r#"{"z": 1, "a": 2, "m": 3}"#).unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"z":1,"a":2,"m":3}"#);
+    let value: JsonValue = serde_json::from_str(r#"{"z": 1, "a": 2, "z": 3}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"z":3,"a":2}"#);
+}
+
+#[test]
+fn maps_numbers_to_the_closest_serde_type() {
+    let options = ParseOptions { big_integers: true, ..ParseOptions::default() };
+    let value = parse_with_options("[-7, 18446744073709551615, 1e400, 2.0]", &options).unwrap();
+    assert_eq!(value[1], JsonValue::BigInt("18446744073709551615".to_string()));
+    // Serde has no non-finite numbers in JSON, so the infinity becomes `null`.
+    assert_eq!(serde_json::to_string(&value).unwrap(), "[-7,18446744073709551615,null,2.0]");
+
+    let options = ParseOptions { preserve_number_strings: true, ..ParseOptions::default() };
+    let value = parse_with_options("[10, -3, 2.50, 1e2]", &options).unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), "[10,-3,2.5,100.0]");
+
+    // Integers read back keep their digits, past `i64` too.
+    let value: JsonValue = serde_json::from_str("[9223372036854775807, 9223372036854775808, -1, 0.5]").unwrap();
+    assert_eq!(value[0], JsonValue::Int(i64::MAX));
+    assert_eq!(value[1], JsonValue::BigInt("9223372036854775808".to_string()));
+    assert_eq!(value[2], JsonValue::Int(-1));
+    assert_eq!(value[3], JsonValue::Float(0.5));
+}
+
+#[test]
+fn reads_rust_values_through_serde() {
+    let value: JsonValue = serde_json::from_value(serde_json::json!({"list": [1, null, "x"], "flag": true})).unwrap();
+    assert_eq!(value, json!({"list": [1, null, "x"], "flag": true}));
+    assert!(serde_json::from_str::<JsonValue>("[1,").is_err());
+}
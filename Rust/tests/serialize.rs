@@ -0,0 +1,331 @@
+use json_parser::{json, parse, parse_with_options, JsonValue, LineEnding, ParseOptions, SerializeOptions};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        },
+        "ratio": -2.5e-3,
+        "notes": "tab\there, \"quotes\" and \\ backslash\n",
+        "nothing": null
+    }
+"##;
+
+#[test]
+fn round_trips_sample_document() {
+    let value = parse(SAMPLE).unwrap();
+    let text = value.to_string();
+    let reparsed = parse(&text).unwrap();
+    assert_eq!(reparsed, value, "{} did not round-trip", text);
+}
+
+#[test]
+fn serializes_compactly() {
+    assert_eq!(parse("[1, 2.5, true, null]").unwrap().to_string(), "[1,2.5,true,null]");
+    assert_eq!(parse(r#"{ "a" : [] }"#).unwrap().to_string(), r#"{"a":[]}"#);
+    assert_eq!(parse("{}").unwrap().to_string(), "{}");
+    assert_eq!(parse("30").unwrap().to_string(), "30");
+}
+
+#[test]
+fn escapes_strings() {
+    let value = JsonValue::Str("\"\\\n\r\t\u{08}\u{0C}\u{01}/北京".to_string());
+    assert_eq!(value.to_string(), r#""\"\\\n\r\t\b\f\u0001/北京""#);
+    assert_eq!(parse(&value.to_string()).unwrap(), value);
+}
+
+#[test]
+fn control_characters_round_trip() {
+    let value = JsonValue::Str("a\u{01}b\u{7f}c\u{1f}\u{0}".to_string());
+    let written = value.to_string();
+    assert_eq!(written, "\"a\\u0001b\\u007fc\\u001f\\u0000\"");
+    assert_eq!(parse(&written).unwrap(), value);
+
+    let members = json!({"\u{01}\u{7f}": ["\u{7f}\u{01}"]});
+    assert_eq!(parse(&members.to_string_pretty(2)).unwrap(), members);
+}
+
+#[test]
+fn writes_non_finite_numbers_as_null() {
+    assert_eq!(JsonValue::Float(f64::NAN).to_string(), "null");
+    assert_eq!(JsonValue::Float(f64::INFINITY).to_string(), "null");
+}
+
+#[test]
+fn pretty_prints_nested_values() {
+    let value = parse(r#"{"user": {"tags": ["a", {"id": 1}, [], {}]}}"#).unwrap();
+    assert_eq!(
+        value.to_string_pretty(2),
+        r#"{
+  "user": {
+    "tags": [
+      "a",
+      {
+        "id": 1
+      },
+      [],
+      {}
+    ]
+  }
+}"#
+    );
+    assert_eq!(parse("[1, [2]]").unwrap().to_string_pretty(4), "[\n    1,\n    [\n        2\n    ]\n]");
+    assert_eq!(parse("[]").unwrap().to_string_pretty(4), "[]");
+}
+
+#[test]
+fn pretty_output_is_reproducible() {
+    let first = parse(SAMPLE).unwrap().to_string_pretty(2);
+    let second = parse(SAMPLE).unwrap().to_string_pretty(2);
+    assert_eq!(first, second);
+    // Members come out in document order, not in some hashed order.
+    let value = parse(SAMPLE).unwrap();
+    let at: Vec<_> =
+        value.as_object().unwrap().keys().map(|k| first.find(&format!("\n  \"{}\"", k)).unwrap()).collect();
+    assert!(at.windows(2).all(|w| w[0] < w[1]), "{}", first);
+}
+
+#[test]
+fn pretty_output_round_trips() {
+    let value = parse(SAMPLE).unwrap();
+    for indent in [0, 2, 4] {
+        assert_eq!(parse(&value.to_string_pretty(indent)).unwrap(), value);
+    }
+}
+
+// Whitespace outside of string literals, which minified output must not have.
+fn has_insignificant_whitespace(json: &str) -> bool {
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in json.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_whitespace() {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn minifies_the_sample_document() {
+    let input = r##"
+        {
+            "nickname": "张三",
+            "age": 30,
+            "is_teacher": false,
+            "scores": [90, 85, 95],
+            "address": {
+                "city": "北京",
+                "street": "中关村大街",
+                "code": [200, 2000]
+            },
+            "motto": "  spaced  out  "
+        }
+    "##;
+    let minified = json_parser::minify(input).unwrap();
+    assert!(!has_insignificant_whitespace(&minified), "{}", minified);
+    assert!(minified.contains(r#""motto":"  spaced  out  ""#));
+    assert_eq!(parse(&minified).unwrap(), parse(input).unwrap());
+    assert!(json_parser::minify("[1,").is_err());
+}
+
+#[test]
+fn canonical_output_ignores_member_order() {
+    let a = parse(r#"{"b": [1, {"y": true, "x": null}], "a": "s", "c": 2.50}"#).unwrap();
+    let b = parse(r#"{ "c": 2.5, "a": "s", "b": [1, {"x": null, "y": true}] }"#).unwrap();
+    assert_eq!(a.to_canonical_string(), r#"{"a":"s","b":[1,{"x":null,"y":true}],"c":2.5}"#);
+    assert_eq!(a.to_canonical_string(), b.to_canonical_string());
+}
+
+#[test]
+fn canonical_output_sorts_by_utf16_code_units() {
+    // U+FB01 sorts after U+1F600 by code point but before it in UTF-16,
+    // where the emoji is a surrogate pair starting at 0xD83D.
+    let value = parse("{\"\u{FB01}\": 1, \"\u{1F600}\": 2, \"a\": 3}").unwrap();
+    assert_eq!(value.to_canonical_string(), "{\"a\":3,\"\u{1F600}\":2,\"\u{FB01}\":1}");
+}
+
+#[test]
+fn canonical_numbers_use_the_shortest_form() {
+    let cases = [
+        ("-0", "0"),
+        ("1.0", "1"),
+        ("1e21", "1e+21"),
+        ("1.5e-7", "1.5e-7"),
+        ("0.000001", "0.000001"),
+        ("123456789", "123456789"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(parse(input).unwrap().to_canonical_string(), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn indents_with_any_string() {
+    let value = parse(r#"{"a": [1, {"b": null}]}"#).unwrap();
+    assert_eq!(value.to_string_indented("\t"), "{\n\t\"a\": [\n\t\t1,\n\t\t{\n\t\t\t\"b\": null\n\t\t}\n\t]\n}");
+    assert_eq!(value.to_string_indented("  "), value.to_string_pretty(2));
+}
+
+#[test]
+fn escapes_forward_slashes_when_asked() {
+    let value = json!({"home/page": "https://example.com/a/b"});
+    assert_eq!(value.to_string(), r#"{"home/page":"https://example.com/a/b"}"#);
+    assert_eq!(value.to_string_with_options(&SerializeOptions::default()), value.to_string());
+
+    let options = SerializeOptions { escape_forward_slashes: true, ..SerializeOptions::default() };
+    let escaped = value.to_string_with_options(&options);
+    assert_eq!(escaped, r#"{"home\/page":"https:\/\/example.com\/a\/b"}"#);
+    assert_eq!(parse(&escaped).unwrap(), value);
+}
+
+#[test]
+fn escapes_non_ascii_when_asked() {
+    let options = SerializeOptions { ascii_only: true, ..SerializeOptions::default() };
+    let value = json!("北京");
+    assert_eq!(value.to_string(), "\"北京\"");
+    let escaped = value.to_string_with_options(&options);
+    assert_eq!(escaped, r#""\u5317\u4eac""#);
+    assert_eq!(parse(&escaped).unwrap(), value);
+
+    // Astral characters become surrogate pairs; ASCII is left alone.
+    let value = json!({"city": "北京 😀", "ok": "plain~"});
+    let escaped = value.to_string_with_options(&options);
+    assert_eq!(escaped, "{\"city\":\"\\u5317\\u4eac \\ud83d\\ude00\",\"ok\":\"plain~\"}");
+    assert!(escaped.is_ascii());
+    assert_eq!(parse(&escaped).unwrap(), value);
+}
+
+#[test]
+fn floats_round_trip_exactly() {
+    for n in [0.1, 1e308, 1.0, -0.0, 5e-324, f64::MAX, 123456.789, 1e15, 1e16, 2.5e-5, 1e-7] {
+        let text = JsonValue::Float(n).to_string();
+        match parse(&text) {
+            Ok(JsonValue::Float(back)) => assert_eq!(back.to_bits(), n.to_bits(), "{} came back from {}", n, text),
+            other => panic!("{} was written as {}, which parsed as {:?}", n, text, other),
+        }
+    }
+}
+
+#[test]
+fn floats_are_written_in_their_shortest_form() {
+    let cases = [(0.1, "0.1"), (1.0, "1.0"), (-0.0, "-0.0"), (1e308, "1e308"), (1.5e-7, "1.5e-7"), (2000.0, "2000.0")];
+    for (n, expected) in cases {
+        assert_eq!(JsonValue::Float(n).to_string(), expected);
+    }
+    assert_eq!(parse("[1, 1.0]").unwrap().to_string(), "[1,1.0]");
+}
+
+#[test]
+fn writes_to_io_writers() {
+    let value = parse(SAMPLE).unwrap();
+    let mut out = Vec::new();
+    value.write_to(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), value.to_string());
+
+    let mut out = Vec::new();
+    value.write_pretty_to(&mut out, "\t").unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), value.to_string_indented("\t"));
+}
+
+#[test]
+fn passes_on_write_errors() {
+    let value = parse(SAMPLE).unwrap();
+    let mut full = [0u8; 10];
+    let err = value.write_to(&mut &mut full[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn sorts_keys_when_asked() {
+    let options = SerializeOptions { sort_keys: true, ..SerializeOptions::default() };
+    let value = parse(r#"{"z": 1, "a": 2}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"z":1,"a":2}"#);
+    assert_eq!(value.to_string_with_options(&options), r#"{"a":2,"z":1}"#);
+
+    let value = parse(r#"{"z": {"y": [{"b": 1, "a": 2}], "x": null}, "a": []}"#).unwrap();
+    assert_eq!(value.to_string_with_options(&options), r#"{"a":[],"z":{"x":null,"y":[{"a":2,"b":1}]}}"#);
+    let pretty = value.to_string_indented_with_options("  ", &options);
+    assert!(pretty.find("\"a\"").unwrap() < pretty.find("\"z\"").unwrap(), "{}", pretty);
+    assert_eq!(parse(&pretty).unwrap(), value);
+    assert_eq!(value.to_string_indented_with_options("  ", &SerializeOptions::default()), value.to_string_pretty(2));
+}
+
+#[test]
+fn rounds_floats_to_a_fixed_precision_when_asked() {
+    let options = |digits| SerializeOptions { float_precision: Some(digits), ..SerializeOptions::default() };
+    assert_eq!(parse("3.14159").unwrap().to_string_with_options(&options(2)), "3.14");
+    assert_eq!(JsonValue::Float(2.5).to_string_with_options(&options(3)), "2.500");
+    assert_eq!(JsonValue::Float(1e-7).to_string_with_options(&options(2)), "0.00");
+
+    // Integers and non-finite floats are left alone, and pretty output rounds too.
+    let mut value = parse(r#"{"pi": 3.14159, "n": 7}"#).unwrap();
+    value.set_pointer("/nan", JsonValue::Float(f64::NAN)).unwrap();
+    assert_eq!(value.to_string_with_options(&options(1)), r#"{"pi":3.1,"n":7,"nan":null}"#);
+    assert_eq!(value.to_string_indented_with_options("", &options(0)), "{\n\"pi\": 3,\n\"n\": 7,\n\"nan\": null\n}");
+}
+
+#[test]
+fn content_hash_ignores_formatting_and_member_order() {
+    let a = parse(r#"{"b": [1, {"y": true, "x": null}], "a": "s", "c": 2.50}"#).unwrap();
+    let b = parse("{ \"c\": 2.5,\n  \"a\": \"s\", \"b\": [1, {\"x\": null, \"y\": true}] }").unwrap();
+    assert_eq!(a.content_hash(), b.content_hash());
+
+    let changed = parse(r#"{"b": [1, {"y": false, "x": null}], "a": "s", "c": 2.50}"#).unwrap();
+    assert_ne!(a.content_hash(), changed.content_hash());
+    let reordered = parse(r#"{"b": [{"y": true, "x": null}, 1], "a": "s", "c": 2.50}"#).unwrap();
+    assert_ne!(a.content_hash(), reordered.content_hash());
+    // The digest is fixed, not seeded per process.
+    assert_eq!(json!(null).content_hash(), 0x5b9b_c4ba_5281_08e4);
+}
+
+#[test]
+fn content_hash_keeps_every_digit_of_large_integers() {
+    let options = ParseOptions { preserve_number_strings: true, ..ParseOptions::default() };
+    let a = parse_with_options("12345678901234567890123", &options).unwrap();
+    let b = parse_with_options("12345678901234567890124", &options).unwrap();
+    assert_ne!(a.content_hash(), b.content_hash());
+    assert_eq!(a.to_canonical_string(), "12345678901234567890123");
+
+    // The sign and leading zeros are normalised, and only numbers with a
+    // fraction or exponent are rounded through `f64`.
+    let cases = [
+        ("-0", "0"),
+        ("-00012345678901234567890", "-12345678901234567890"),
+        ("+42", "42"),
+        ("12345678901234567890.0", "12345678901234567000"),
+        ("1.5e3", "1500"),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(JsonValue::RawNum(raw.to_string()).to_canonical_string(), expected, "{:?}", raw);
+    }
+    assert_eq!(JsonValue::RawNum("42".to_string()).content_hash(), json!(42).content_hash());
+}
+
+#[test]
+fn indented_output_can_use_crlf() {
+    let options = SerializeOptions { line_ending: LineEnding::Crlf, ..SerializeOptions::default() };
+    let value = json!({"a": 1, "b": [true]});
+    assert_eq!(
+        value.to_string_indented_with_options("  ", &options),
+        "{\r\n  \"a\": 1,\r\n  \"b\": [\r\n    true\r\n  ]\r\n}"
+    );
+    // Compact output has no line breaks to change.
+    assert_eq!(value.to_string_with_options(&options), r#"{"a":1,"b":[true]}"#);
+    let lf = parse(SAMPLE).unwrap().to_string_pretty(2);
+    assert_eq!(parse(SAMPLE).unwrap().to_string_indented_with_options("  ", &options), lf.replace('\n', "\r\n"));
+}
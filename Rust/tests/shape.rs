@@ -0,0 +1,63 @@
+use json_parser::{parse, Shape};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+fn object(members: &[(&str, Shape)]) -> Shape {
+    Shape::Object(members.iter().map(|(key, shape)| (key.to_string(), shape.clone())).collect())
+}
+
+fn array(element: Shape) -> Shape {
+    Shape::Array(Box::new(element))
+}
+
+#[test]
+fn matches_the_sample_document() {
+    let sample = parse(SAMPLE).unwrap();
+    let shape = object(&[
+        ("nickname", Shape::String),
+        ("age", Shape::Number),
+        ("is_teacher", Shape::Bool),
+        ("scores", array(Shape::Number)),
+        ("address", object(&[("city", Shape::String), ("code", array(Shape::Any))])),
+    ]);
+    assert!(sample.matches_shape(&shape));
+    assert!(sample.matches_shape(&object(&[("address", object(&[("city", Shape::String)]))])));
+    assert!(sample.matches_shape(&Shape::Any));
+    assert!(sample.matches_shape(&object(&[])));
+}
+
+#[test]
+fn rejects_mismatches_in_the_sample_document() {
+    let sample = parse(SAMPLE).unwrap();
+    for shape in [
+        object(&[("address", object(&[("city", Shape::Number)]))]),
+        object(&[("address", object(&[("zip", Shape::Any)]))]),
+        object(&[("scores", array(Shape::String))]),
+        object(&[("age", Shape::Null)]),
+        object(&[("address", Shape::String)]),
+        array(Shape::Any),
+    ] {
+        assert!(!sample.matches_shape(&shape), "{:?}", shape);
+    }
+}
+
+#[test]
+fn matches_scalars_and_empty_arrays() {
+    assert!(parse("null").unwrap().matches_shape(&Shape::Null));
+    assert!(parse("1.5").unwrap().matches_shape(&Shape::Number));
+    assert!(!parse("\"1\"").unwrap().matches_shape(&Shape::Number));
+    assert!(parse("[]").unwrap().matches_shape(&array(Shape::Bool)));
+    assert!(parse("[[true], []]").unwrap().matches_shape(&array(array(Shape::Bool))));
+}
@@ -0,0 +1,31 @@
+use std::rc::Rc;
+
+use json_parser::{json, parse, JsonValueRc};
+
+#[test]
+fn clones_share_their_children() {
+    let subtree = Rc::new(JsonValueRc::from(parse(r#"{"items": [1, 2, 3], "name": "big"}"#).unwrap()));
+    let document =
+        JsonValueRc::Object(vec![("first".to_string(), subtree.clone()), ("second".to_string(), subtree.clone())]);
+    assert!(Rc::ptr_eq(document.get("first").unwrap(), document.get("second").unwrap()));
+    assert_eq!(Rc::strong_count(&subtree), 3);
+
+    let copy = document.clone();
+    assert!(Rc::ptr_eq(copy.get("first").unwrap(), &subtree));
+    assert_eq!(copy, document);
+
+    let whole = Rc::new(document);
+    let handle = whole.clone();
+    assert!(Rc::ptr_eq(&whole, &handle));
+}
+
+#[test]
+fn converts_to_and_from_owned_values() {
+    let value = json!({"b": [1, {"c": null}], "a": "s", "n": 2.5});
+    let shared = JsonValueRc::from(value.clone());
+    assert_eq!(**shared.get("b").unwrap().get_index(0).unwrap(), JsonValueRc::Int(1));
+    assert!(shared.get("missing").is_none());
+    assert!(shared.get_index(0).is_none());
+    assert_eq!(shared.to_value(), value);
+    assert_eq!(shared.to_value().to_string(), value.to_string());
+}
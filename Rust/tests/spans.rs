@@ -0,0 +1,54 @@
+use json_parser::{parse, parse_spanned, Spanned, SpannedValue};
+
+const INPUT: &str = r#"{
+  "name": "张三",
+  "age": 30,
+  "tags": [true, null, {"k": "v"}]
+}"#;
+
+fn source(value: &Spanned<SpannedValue>) -> &'static str {
+    &INPUT[value.span.clone()]
+}
+
+#[test]
+fn records_the_span_of_every_value() {
+    let root = parse_spanned(INPUT).unwrap();
+    assert_eq!(root.span, 0..INPUT.len());
+
+    let age = root.value.get("age").unwrap();
+    assert_eq!(age.value, SpannedValue::Int(30));
+    let start = INPUT.find("30").unwrap();
+    assert_eq!(age.span, start..start + 2);
+
+    // Spans are in bytes, and a string's includes its quotes.
+    assert_eq!(source(root.value.get("name").unwrap()), "\"张三\"");
+
+    let tags = root.value.get("tags").unwrap();
+    assert_eq!(source(tags), r#"[true, null, {"k": "v"}]"#);
+    let SpannedValue::Array(items) = &tags.value else {
+        panic!("tags should be an array");
+    };
+    let sources: Vec<_> = items.iter().map(source).collect();
+    assert_eq!(sources, ["true", "null", r#"{"k": "v"}"#]);
+    assert_eq!(source(items[2].value.get("k").unwrap()), "\"v\"");
+}
+
+#[test]
+fn spans_exclude_surrounding_whitespace() {
+    let value = parse_spanned("  [ 1 ,[]]\n").unwrap();
+    assert_eq!(value.span, 2..10);
+    let SpannedValue::Array(items) = &value.value else {
+        panic!("expected an array");
+    };
+    assert_eq!(items[0].span, 4..5);
+    assert_eq!(items[1].span, 7..9);
+
+    // Offsets count from the start of the input, byte order mark included.
+    assert_eq!(parse_spanned("\u{FEFF}7").unwrap().span, 3..4);
+}
+
+#[test]
+fn converts_into_the_parsed_value() {
+    assert_eq!(parse_spanned(INPUT).unwrap().value.into_value(), parse(INPUT).unwrap());
+    assert!(parse_spanned("[1,").is_err());
+}
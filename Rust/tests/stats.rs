@@ -0,0 +1,41 @@
+use json_parser::{parse, parse_with_stats, ParseStats};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+#[test]
+fn counts_the_sample_document() {
+    let (value, stats) = parse_with_stats(SAMPLE).unwrap();
+    assert_eq!(value, parse(SAMPLE).unwrap());
+    assert_eq!(stats, ParseStats { max_depth: 3, nodes: 14, objects: 2, arrays: 2, strings: 3, numbers: 6 });
+}
+
+#[test]
+fn scalars_have_no_depth() {
+    let (_, stats) = parse_with_stats("\"hi\"").unwrap();
+    assert_eq!(stats, ParseStats { nodes: 1, strings: 1, ..ParseStats::default() });
+    let (_, stats) = parse_with_stats("[[], [[]], {}]").unwrap();
+    assert_eq!(stats, ParseStats { max_depth: 3, nodes: 5, objects: 1, arrays: 4, ..ParseStats::default() });
+}
+
+#[test]
+fn keys_are_not_counted_as_strings() {
+    let (_, stats) = parse_with_stats(r#"{"a": null, "b": true, "a": 1.5}"#).unwrap();
+    assert_eq!(stats, ParseStats { max_depth: 1, nodes: 4, objects: 1, numbers: 1, ..ParseStats::default() });
+}
+
+#[test]
+fn errors_are_the_same_as_parse() {
+    assert_eq!(parse_with_stats("[1,").unwrap_err().to_string(), parse("[1,").unwrap_err().to_string());
+}
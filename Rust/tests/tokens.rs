@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+
+use json_parser::{tokenize, ParseError, Spanned, Token};
+
+fn tokens(input: &str) -> Vec<(Token<'_>, std::ops::Range<usize>)> {
+    tokenize(input).map(|token| token.map(|Spanned { value, span }| (value, span)).unwrap()).collect()
+}
+
+#[test]
+fn tokenizes_with_spans() {
+    assert_eq!(
+        tokens(r#"{"a":[1]}"#),
+        [
+            (Token::LBrace, 0..1),
+            (Token::String(Cow::Borrowed("a")), 1..4),
+            (Token::Colon, 4..5),
+            (Token::LBracket, 5..6),
+            (Token::Number("1"), 6..7),
+            (Token::RBracket, 7..8),
+            (Token::RBrace, 8..9),
+        ]
+    );
+}
+
+#[test]
+fn skips_whitespace_and_decodes_strings() {
+    assert_eq!(
+        tokens(" [ -1.5e3 ,\n\"\\u5317\" , false, null ] "),
+        [
+            (Token::LBracket, 1..2),
+            (Token::Number("-1.5e3"), 3..9),
+            (Token::Comma, 10..11),
+            (Token::String(Cow::Owned("北".to_string())), 12..20),
+            (Token::Comma, 21..22),
+            (Token::Bool(false), 23..28),
+            (Token::Comma, 28..29),
+            (Token::Null, 30..34),
+            (Token::RBracket, 35..36),
+        ]
+    );
+    assert!(tokens("  ").is_empty());
+}
+
+#[test]
+fn does_not_check_the_grammar() {
+    let tokens: Vec<_> = tokens("] 1 2 :").into_iter().map(|(token, _)| token).collect();
+    assert_eq!(tokens, [Token::RBracket, Token::Number("1"), Token::Number("2"), Token::Colon]);
+}
+
+#[test]
+fn stops_at_the_first_error() {
+    let results: Vec<_> = tokenize("[1, 01, 2]").collect();
+    assert_eq!(results.len(), 4);
+    assert!(matches!(&results[3], Err(ParseError::InvalidNumber { position }) if position.offset == 5));
+
+    let results: Vec<_> = tokenize("[\"\\q\"] x").collect();
+    assert!(matches!(results.last(), Some(Err(ParseError::InvalidEscape { .. }))));
+    assert!(matches!(tokenize("@").next(), Some(Err(ParseError::UnexpectedChar { found: '@', .. }))));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::allocations_during;
+use json_parser::{parse, validate};
+
+const INPUTS: &[&str] = &[
+    "null",
+    "  [1, 2.5, -0, 1e10, true, false, null] ",
+    r#"{"nickname": "张三", "address": {"city": "北京", "code": [200, 2000]}}"#,
+    r#"["esc\"aped", "北😀", "\n"]"#,
+    "\u{FEFF}{}",
+    "",
+    "[1, 2",
+    "[1,]",
+    "{\"a\" 1}",
+    "01",
+    "1.",
+    r#""\q""#,
+    r#""\uDE00""#,
+    "\"raw\ttab\"",
+    "{} trailing",
+    "[[[]]",
+];
+
+#[test]
+fn agrees_with_parse() {
+    for input in INPUTS {
+        match (validate(input), parse(input)) {
+            (Ok(()), Ok(_)) => {}
+            (Err(a), Err(b)) => {
+                assert_eq!(a.to_string(), b.to_string(), "{:?}", input);
+                assert_eq!(a.position(), b.position(), "{:?}", input);
+            }
+            (a, b) => panic!("validate gave {:?} but parse gave {:?} for {:?}", a, b, input),
+        }
+    }
+}
+
+#[test]
+fn enforces_the_default_depth_limit() {
+    let deep = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+    assert!(validate(&deep).is_err());
+    assert!(validate(&format!("{}{}", "[".repeat(100), "]".repeat(100))).is_ok());
+}
+
+#[test]
+fn allocates_nothing() {
+    let nested = |depth: usize| format!("{}1{}", "[{\"a\": ".repeat(depth / 2), "}]".repeat(depth / 2));
+    let large = format!("[{}]", vec![r#"{"s": "text\n", "n": [1.5, true, null]}"#; 1000].join(","));
+    for input in INPUTS.iter().copied().chain([r#"[1, {"a": [true, "x"]}]"#, &nested(32), &large]) {
+        let (result, allocations) = allocations_during(|| validate(input));
+        assert_eq!(allocations, 0, "{:?} validated as {:?}", input, result);
+    }
+    for input in [nested(32), large] {
+        assert!(validate(&input).is_ok());
+    }
+    // Deeper nesting spills the stack of open containers onto the heap, but
+    // the number of allocations doesn't grow with the size of the document.
+    let deep = nested(100);
+    let (result, allocations) = allocations_during(|| validate(&deep));
+    assert!(result.is_ok());
+    assert!((1..10).contains(&allocations), "{} allocations", allocations);
+}
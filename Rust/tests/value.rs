@@ -0,0 +1,489 @@
+use std::collections::{HashMap, HashSet};
+
+use json_parser::{json, parse, JsonValue, Map, TypeError};
+
+#[test]
+fn equal_values_compare_equal() {
+    assert_eq!(parse("[1, \"a\", [true, null]]").unwrap(), parse("[1,\"a\",[true,null]]").unwrap());
+    assert_eq!(
+        parse(r#"{"a": {"b": [1, 2]}, "c": 3.5}"#).unwrap(),
+        parse(r#"{"c": 3.5, "a": {"b": [1, 2]}}"#).unwrap()
+    );
+}
+
+#[test]
+fn different_values_compare_unequal() {
+    assert_ne!(parse("[1, 2]").unwrap(), parse("[2, 1]").unwrap());
+    assert_ne!(parse("[1, 2]").unwrap(), parse("[1, 2, 3]").unwrap());
+    assert_ne!(parse(r#"{"a": {"b": 1}}"#).unwrap(), parse(r#"{"a": {"b": 2}}"#).unwrap());
+    assert_ne!(parse(r#"{"a": 1}"#).unwrap(), parse(r#"{"b": 1}"#).unwrap());
+    assert_ne!(parse("null").unwrap(), parse("false").unwrap());
+}
+
+#[test]
+fn nan_is_not_equal_to_itself() {
+    assert_ne!(JsonValue::Float(f64::NAN), JsonValue::Float(f64::NAN));
+    assert_eq!(JsonValue::Float(0.0), JsonValue::Float(-0.0));
+}
+
+#[test]
+fn clones_are_independent() {
+    let original = parse(r#"{"name": "John", "scores": [90, 85]}"#).unwrap();
+    let mut copy = original.clone();
+    assert_eq!(copy, original);
+
+    let JsonValue::Object(members) = &mut copy else { unreachable!() };
+    members.insert("name".to_string(), JsonValue::Str("Jane".to_string()));
+    if let Some(JsonValue::Array(scores)) = members.get_mut("scores") {
+        scores.push(JsonValue::Int(70));
+    }
+
+    assert_ne!(copy, original);
+    assert_eq!(original, parse(r#"{"name": "John", "scores": [90, 85]}"#).unwrap());
+}
+
+#[test]
+fn accessors_match_their_variant() {
+    let s = parse(r#""hi""#).unwrap();
+    let b = parse("true").unwrap();
+    let n = parse("2.5").unwrap();
+    let a = parse("[1]").unwrap();
+    let o = parse(r#"{"k": null}"#).unwrap();
+    let null = parse("null").unwrap();
+
+    assert_eq!(s.as_str(), Some("hi"));
+    assert_eq!(b.as_bool(), Some(true));
+    assert_eq!(n.as_f64(), Some(2.5));
+    assert_eq!(a.as_array(), Some(&vec![JsonValue::Int(1)]));
+    assert_eq!(o.as_object().map(|m| m.len()), Some(1));
+
+    for other in [&b, &n, &a, &o, &null] {
+        assert_eq!(other.as_str(), None);
+    }
+    for other in [&s, &n, &a, &o, &null] {
+        assert_eq!(other.as_bool(), None);
+    }
+    for other in [&s, &b, &a, &o, &null] {
+        assert_eq!(other.as_f64(), None);
+    }
+    for other in [&s, &b, &n, &o, &null] {
+        assert_eq!(other.as_array(), None);
+    }
+    for other in [&s, &b, &n, &a, &null] {
+        assert_eq!(other.as_object(), None);
+    }
+}
+
+#[test]
+fn indexes_by_key_and_position() {
+    let value = parse(r#"{"address": {"city": "北京", "code": [200, 2000]}, "scores": [90, 85]}"#).unwrap();
+    assert_eq!(value["address"]["city"], JsonValue::Str("北京".to_string()));
+    assert_eq!(value["address"]["code"][1], JsonValue::Int(2000));
+    assert_eq!(value["scores"][0], JsonValue::Int(90));
+}
+
+#[test]
+fn indexing_missing_entries_yields_null() {
+    let value = parse(r#"{"scores": [90, 85]}"#).unwrap();
+    assert_eq!(value["missing"], JsonValue::Null);
+    assert_eq!(value["missing"]["deeper"], JsonValue::Null);
+    assert_eq!(value["scores"][5], JsonValue::Null);
+    assert_eq!(value["scores"]["key"], JsonValue::Null);
+    assert_eq!(value[0], JsonValue::Null);
+}
+
+#[test]
+fn get_navigates_safely() {
+    let value = parse(r#"{"a": {"b": [10, 20]}, "s": "text"}"#).unwrap();
+    assert_eq!(value.get("a").and_then(|v| v.get("b")).and_then(|v| v.get_index(1)), Some(&JsonValue::Int(20)));
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(value.get("a").and_then(|v| v.get("c")), None);
+    assert_eq!(value["a"]["b"].get_index(2), None);
+    assert_eq!(value["s"].get("a"), None);
+    assert_eq!(value.get_index(0), None);
+}
+
+#[test]
+fn builds_values_with_from() {
+    let mut address = HashMap::new();
+    address.insert("city".to_string(), JsonValue::from("北京"));
+    let mut root = Map::new();
+    root.insert("name".to_string(), JsonValue::from(String::from("John")));
+    root.insert("age".to_string(), JsonValue::from(30i64));
+    root.insert("height".to_string(), JsonValue::from(1.75));
+    root.insert("is_teacher".to_string(), JsonValue::from(false));
+    root.insert("scores".to_string(), JsonValue::from(vec![JsonValue::from(90), JsonValue::from(85u8)]));
+    root.insert("address".to_string(), JsonValue::from(address));
+    root.insert("spouse".to_string(), JsonValue::from(()));
+    let value = JsonValue::from(root);
+
+    assert_eq!(
+        value.to_string(),
+        r#"{"name":"John","age":30,"height":1.75,"is_teacher":false,"scores":[90,85],"address":{"city":"北京"},"spouse":null}"#
+    );
+}
+
+#[test]
+fn iterates_entries_and_elements() {
+    let value = parse(r#"{"nickname": "张三", "age": 30, "scores": [90, 85, 95]}"#).unwrap();
+    let keys: Vec<&str> = value.entries().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["nickname", "age", "scores"]);
+    let total: i64 = value["scores"].elements().filter_map(JsonValue::as_i64).sum();
+    assert_eq!(total, 270);
+
+    assert_eq!(value.elements().count(), 0);
+    assert_eq!(value["scores"].entries().count(), 0);
+    assert_eq!(JsonValue::from(1).entries().count(), 0);
+    assert_eq!(JsonValue::from("s").elements().count(), 0);
+}
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "is_teacher": false,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        }
+    }
+"##;
+
+#[test]
+fn walk_visits_every_node() {
+    let value = parse(SAMPLE).unwrap();
+    let mut numbers = 0;
+    let mut nodes = 0;
+    value.walk(|node| {
+        nodes += 1;
+        if matches!(node, JsonValue::Int(_) | JsonValue::Float(_)) {
+            numbers += 1;
+        }
+    });
+    assert_eq!(numbers, 6);
+    assert_eq!(nodes, 14);
+}
+
+#[test]
+fn walk_visits_parents_before_children() {
+    let value = parse(r#"{"a": [1, {"b": 2}], "c": 3}"#).unwrap();
+    let mut order = Vec::new();
+    value.walk(|node| order.push(node.to_string()));
+    assert_eq!(order, [r#"{"a":[1,{"b":2}],"c":3}"#, r#"[1,{"b":2}]"#, "1", r#"{"b":2}"#, "2", "3"]);
+}
+
+#[test]
+fn collects_every_string() {
+    let value = parse(SAMPLE).unwrap();
+    assert_eq!(value.collect_strings(false), ["张三", "北京", "中关村大街"]);
+    assert_eq!(
+        value.collect_strings(true),
+        ["nickname", "张三", "age", "is_teacher", "scores", "address", "city", "北京", "street", "中关村大街", "code"]
+    );
+    assert!(parse("[1, null]").unwrap().collect_strings(true).is_empty());
+}
+
+#[test]
+fn loose_equality_ignores_the_number_variant() {
+    let int = parse("1").unwrap();
+    let float = parse("1.0").unwrap();
+    assert_ne!(int, float);
+    assert!(int.loosely_eq(&float));
+    assert!(float.loosely_eq(&int));
+
+    let a = parse(r#"{"n": [1, 2.5, {"x": -3}], "s": "a"}"#).unwrap();
+    let b = parse(r#"{"s": "a", "n": [1.0, 2.5, {"x": -3e0}]}"#).unwrap();
+    assert_ne!(a, b);
+    assert!(a.loosely_eq(&b));
+
+    assert!(!int.loosely_eq(&parse("1.5").unwrap()));
+    assert!(!int.loosely_eq(&parse("\"1\"").unwrap()));
+    assert!(!parse("[1]").unwrap().loosely_eq(&parse("[1, 1]").unwrap()));
+    assert!(!parse("1e300").unwrap().loosely_eq(&JsonValue::Int(i64::MAX)));
+}
+
+#[test]
+fn exactly_one_type_predicate_holds() {
+    let values = parse(r#"[null, true, 1, 1.5, "s", [], {}]"#).unwrap();
+    let expected = ["null", "bool", "number", "number", "string", "array", "object"];
+    for (value, expected) in values.elements().zip(expected) {
+        let predicates = [
+            ("null", value.is_null()),
+            ("bool", value.is_bool()),
+            ("number", value.is_number()),
+            ("string", value.is_string()),
+            ("array", value.is_array()),
+            ("object", value.is_object()),
+        ];
+        let holding: Vec<_> = predicates.iter().filter(|(_, holds)| *holds).map(|(name, _)| *name).collect();
+        assert_eq!(holding, [expected], "{:?}", value);
+    }
+}
+
+#[test]
+fn into_conversions_take_ownership() {
+    assert_eq!(JsonValue::from("owned").into_string(), Ok("owned".to_string()));
+    assert_eq!(parse("[1, 2]").unwrap().into_array(), Ok(vec![JsonValue::Int(1), JsonValue::Int(2)]));
+
+    let members = parse(r#"{"b": 1, "a": 2}"#).unwrap().into_object().unwrap();
+    assert_eq!(members.keys().collect::<Vec<_>>(), ["b", "a"]);
+    let members: HashMap<String, JsonValue> = members.into_iter().collect();
+    assert_eq!(members["a"], JsonValue::Int(2));
+}
+
+#[test]
+fn into_conversions_return_mismatches() {
+    assert_eq!(JsonValue::Int(3).into_string(), Err(JsonValue::Int(3)));
+    assert_eq!(JsonValue::from("s").into_array(), Err(JsonValue::from("s")));
+    let array = parse("[{}]").unwrap();
+    assert_eq!(array.clone().into_object(), Err(array));
+    assert_eq!(JsonValue::Null.into_object(), Err(JsonValue::Null));
+}
+
+#[test]
+fn into_iter_drains_arrays_by_value() {
+    let value = parse(r#"["a", {"b": [1]}, null]"#).unwrap();
+    let mut strings = Vec::new();
+    for item in value {
+        if let Ok(s) = item.into_string() {
+            strings.push(s);
+        }
+    }
+    assert_eq!(strings, ["a"]);
+
+    let owned: Vec<JsonValue> = parse("[[1], [2, 3]]").unwrap().into_iter().flatten().collect();
+    assert_eq!(owned, [JsonValue::Int(1), JsonValue::Int(2), JsonValue::Int(3)]);
+
+    assert_eq!(parse(r#"{"a": 1}"#).unwrap().into_iter().count(), 0);
+    assert_eq!(JsonValue::from("s").into_iter().next(), None);
+    assert_eq!(JsonValue::Null.into_iter().len(), 0);
+}
+
+#[test]
+fn equal_values_hash_alike() {
+    let mut set = HashSet::new();
+    assert!(set.insert(parse(r#"{"a": 1, "b": [true, {"c": null, "d": "x"}]}"#).unwrap()));
+    assert!(!set.insert(parse(r#"{"b": [true, {"d": "x", "c": null}], "a": 1}"#).unwrap()));
+    // Arrays are ordered, and an int is never equal to a float.
+    assert!(set.insert(parse(r#"{"a": 1, "b": [{"c": null, "d": "x"}, true]}"#).unwrap()));
+    assert!(set.insert(parse(r#"{"a": 1.0, "b": [true, {"c": null, "d": "x"}]}"#).unwrap()));
+    assert_eq!(set.len(), 3);
+
+    assert!(set.insert(JsonValue::Float(0.0)));
+    assert!(!set.insert(JsonValue::Float(-0.0)));
+    assert!(set.insert(JsonValue::Float(f64::NAN)));
+    assert!(set.insert(JsonValue::Float(f64::NAN)));
+}
+
+#[test]
+fn builds_values_fluently() {
+    let built = JsonValue::object()
+        .insert("nickname", "张三")
+        .insert("age", 30)
+        .insert("scores", JsonValue::array().push(90).push(85).push(95))
+        .insert(
+            "address",
+            JsonValue::object().insert("city", "北京").insert("code", JsonValue::array().push(200).build()),
+        )
+        .insert("nothing", ())
+        .build();
+    let parsed = parse(
+        r#"{"nickname": "张三", "age": 30, "scores": [90, 85, 95],
+            "address": {"city": "北京", "code": [200]}, "nothing": null}"#,
+    )
+    .unwrap();
+    assert_eq!(built, parsed);
+    assert_eq!(built.to_string(), parsed.to_string());
+
+    assert_eq!(JsonValue::object().build(), parse("{}").unwrap());
+    assert_eq!(JsonValue::array().build(), parse("[]").unwrap());
+}
+
+#[test]
+fn pushes_onto_arrays_only() {
+    let mut value = parse("[1]").unwrap();
+    value.push(JsonValue::from("two")).unwrap();
+    value.extend([JsonValue::Null, JsonValue::from(4)]).unwrap();
+    value.extend(parse("[5, 6]").unwrap()).unwrap();
+    assert_eq!(value, parse(r#"[1, "two", null, 4, 5, 6]"#).unwrap());
+
+    let mut object = parse(r#"{"a": 1}"#).unwrap();
+    let err = object.push(JsonValue::Null).unwrap_err();
+    assert_eq!(err, TypeError { expected: "an array", found: "an object" });
+    assert_eq!(err.to_string(), "expected an array, found an object");
+    assert!(object.extend(Vec::new()).is_err());
+    assert_eq!(object, parse(r#"{"a": 1}"#).unwrap());
+}
+
+#[test]
+fn inserts_and_removes_object_members() {
+    let mut value = parse(r#"{"a": 1, "b": 2}"#).unwrap();
+    assert_eq!(value.insert("c", JsonValue::from(3)), Ok(None));
+    assert_eq!(value.insert(String::from("a"), JsonValue::from("one")), Ok(Some(JsonValue::from(1))));
+    assert_eq!(value.to_string(), r#"{"a":"one","b":2,"c":3}"#);
+    assert!(value.contains_key("b"));
+
+    assert_eq!(value.remove("b"), Some(JsonValue::from(2)));
+    assert_eq!(value.remove("b"), None);
+    assert!(!value.contains_key("b"));
+    assert_eq!(value.to_string(), r#"{"a":"one","c":3}"#);
+
+    let mut array = parse("[1]").unwrap();
+    assert_eq!(array.insert("a", JsonValue::Null), Err(TypeError { expected: "an object", found: "an array" }));
+    assert_eq!(array.remove("0"), None);
+    assert!(!array.contains_key("0"));
+    assert_eq!(array, parse("[1]").unwrap());
+}
+
+#[test]
+fn sorts_arrays_of_scalars() {
+    let mut value = parse(r#"{"scores": [95, 90, 85], "nested": [[3, 1, 2], {"tags": ["b", "a"]}, 0]}"#).unwrap();
+    value.sort_scalar_arrays();
+    assert_eq!(value["scores"], parse("[85, 90, 95]").unwrap());
+    // The mixed array keeps its order, but what's inside it is sorted.
+    assert_eq!(value["nested"], parse(r#"[[1, 2, 3], {"tags": ["a", "b"]}, 0]"#).unwrap());
+
+    let mut value = parse(r#"["b", 2.5, true, null, "a", -1, false, 2, 1e0]"#).unwrap();
+    value.sort_scalar_arrays();
+    assert_eq!(value, parse(r#"[null, false, true, -1, 1e0, 2, 2.5, "a", "b"]"#).unwrap());
+}
+
+#[test]
+fn sorts_numbers_exactly_whatever_their_variant() {
+    let raw = |s: &str| JsonValue::RawNum(s.to_string());
+    let big = |s: &str| JsonValue::BigInt(s.to_string());
+    // Each is smaller than the next, though rounding them all to `f64`
+    // would make many of them equal.
+    let ascending = vec![
+        JsonValue::Float(f64::NEG_INFINITY),
+        big("-100000000000000000001"),
+        JsonValue::Float(-1e20),
+        big("-99999999999999999999"),
+        JsonValue::Int(i64::MIN),
+        raw("0.5"),
+        JsonValue::Int(9007199254740992),
+        raw("9007199254740993"),
+        JsonValue::Float(9007199254740994.0),
+        JsonValue::Int(i64::MAX - 1),
+        JsonValue::Int(i64::MAX),
+        JsonValue::Float(9223372036854775808.0),
+        big("9223372036854775809"),
+        raw("1e30"),
+        JsonValue::Float(f64::INFINITY),
+        JsonValue::Float(f64::NAN),
+    ];
+    // Every ordering of pairs agrees, so the sort is the same from any start.
+    for shift in 0..ascending.len() {
+        let mut rotated = ascending.clone();
+        rotated.rotate_left(shift);
+        rotated.reverse();
+        assert_eq!(sorted(rotated), format!("{:?}", ascending), "starting from rotation {}", shift);
+    }
+
+    // Equal values written differently sort as ties, keeping their order.
+    let ties = vec![raw("-0"), JsonValue::Float(0.5), JsonValue::Int(0), raw("0.0"), big("0")];
+    assert_eq!(
+        sorted(ties),
+        format!("{:?}", [raw("-0"), JsonValue::Int(0), raw("0.0"), big("0"), JsonValue::Float(0.5)])
+    );
+}
+
+// The array sorted, in `Debug` form so that the variants and NaN count.
+fn sorted(items: Vec<JsonValue>) -> String {
+    let mut value = JsonValue::Array(items);
+    value.sort_scalar_arrays();
+    format!("{:?}", value.as_array().unwrap())
+}
+
+#[test]
+fn coerces_sloppy_values_to_numbers() {
+    assert_eq!(json!("3.5").coerce_to_number(), Some(3.5));
+    assert_eq!(json!(true).coerce_to_number(), Some(1.0));
+    assert_eq!(json!(false).coerce_to_number(), Some(0.0));
+    assert_eq!(json!(null).coerce_to_number(), Some(0.0));
+    assert_eq!(json!(7).coerce_to_number(), Some(7.0));
+    assert_eq!(json!("\t-1e3 ").coerce_to_number(), Some(-1000.0));
+    assert_eq!(json!("").coerce_to_number(), Some(0.0));
+    assert_eq!(json!("-Infinity").coerce_to_number(), Some(f64::NEG_INFINITY));
+    for value in [json!("abc"), json!("1 2"), json!("inf"), json!("NaN"), json!({"a": 1}), json!([1])] {
+        assert_eq!(value.coerce_to_number(), None, "{}", value);
+    }
+}
+
+#[test]
+fn coerces_values_to_bool_by_truthiness() {
+    for value in [json!(null), json!(false), json!(0), json!(-0.0), json!(""), JsonValue::Float(f64::NAN)] {
+        assert!(!value.coerce_to_bool(), "{}", value);
+    }
+    for value in [json!(true), json!(-1), json!(0.5), json!("false"), json!("0"), json!([]), json!({})] {
+        assert!(value.coerce_to_bool(), "{}", value);
+    }
+}
+
+#[test]
+fn take_leaves_null_behind() {
+    let mut doc = parse(r#"{"user": {"scores": [90, [85, 95]], "name": "张三"}}"#).unwrap();
+    let taken = doc.pointer_mut("/user/scores/1").unwrap().take();
+    assert_eq!(taken, json!([85, 95]));
+    assert_eq!(doc, json!({"user": {"scores": [90, null], "name": "张三"}}));
+
+    let mut null = json!(null);
+    assert_eq!(null.take(), json!(null));
+    assert_eq!(null, json!(null));
+}
+
+#[test]
+fn map_values_replaces_and_drops_scalars() {
+    let doc = parse(r#"{"name": "张三", "age": 30, "tags": ["a", null, "b"], "a/b": {"x": null, "y": "z"}, "e": []}"#)
+        .unwrap();
+    let mut paths = Vec::new();
+    let redacted = doc.map_values(|path, value| {
+        paths.push(path.to_string());
+        match value {
+            JsonValue::Null => None,
+            JsonValue::Str(_) => Some(json!("REDACTED")),
+            other => Some(other),
+        }
+    });
+    assert_eq!(
+        redacted,
+        json!({"name": "REDACTED", "age": 30, "tags": ["REDACTED", "REDACTED"], "a/b": {"y": "REDACTED"}, "e": []})
+    );
+    assert_eq!(paths, ["/name", "/age", "/tags/0", "/tags/1", "/tags/2", "/a~1b/x", "/a~1b/y"]);
+
+    assert_eq!(json!(1).map_values(|path, _| Some(json!(path))), json!(""));
+    assert_eq!(json!(null).map_values(|_, _| None), json!(null));
+}
+
+#[test]
+fn measures_strings_in_utf16_code_units() {
+    let emoji = parse(r#""\ud83d\ude00""#).unwrap();
+    assert_eq!(emoji.string_utf16_len(), Some(2));
+    assert_eq!(emoji.as_str().unwrap().chars().count(), 1);
+    assert_eq!(emoji.as_str().unwrap().len(), 4);
+
+    assert_eq!(json!("北京 ok").string_utf16_len(), Some(5));
+    assert_eq!(json!("").string_utf16_len(), Some(0));
+    assert_eq!(json!(["a"]).string_utf16_len(), None);
+}
+
+#[test]
+fn estimates_heap_size_from_contents() {
+    let empty = json!([]);
+    assert_eq!(empty.estimated_heap_size(), 0);
+    assert_eq!(json!(42).estimated_heap_size(), 0);
+
+    let strings = JsonValue::Array((0..100).map(|i| JsonValue::from(format!("string number {:03}", i))).collect());
+    // At least the 100 strings' 17 bytes each and a slot for each of them.
+    let size = strings.estimated_heap_size();
+    assert!(size >= 100 * (17 + std::mem::size_of::<JsonValue>()), "{}", size);
+    assert!(size > empty.estimated_heap_size() + 2_000);
+
+    // Objects count their keys and values, and nesting adds up.
+    let object = json!({"name": "张三", "scores": [90, 85]});
+    let nested = json!({"inner": {"name": "张三", "scores": [90, 85]}});
+    assert!(object.estimated_heap_size() > "name".len() + "张三".len() + "scores".len());
+    assert!(nested.estimated_heap_size() > object.estimated_heap_size());
+}
@@ -0,0 +1,55 @@
+use json_parser::{json, parse};
+
+const SAMPLE: &str = r##"
+    {
+        "nickname": "张三",
+        "age": 30,
+        "scores": [90, 85, 95],
+        "address": {
+            "city": "北京",
+            "street": "中关村大街",
+            "code": [200, 2000]
+        },
+        "nothing": null
+    }
+"##;
+
+#[test]
+fn renders_nested_objects_and_arrays() {
+    let value = parse(SAMPLE).unwrap();
+    assert_eq!(value["address"].to_yaml_like(), "city: 北京\nstreet: 中关村大街\ncode:\n  - 200\n  - 2000");
+    assert_eq!(
+        value.to_yaml_like(),
+        "nickname: 张三
+age: 30
+scores:
+  - 90
+  - 85
+  - 95
+address:
+  city: 北京
+  street: 中关村大街
+  code:
+    - 200
+    - 2000
+nothing: null"
+    );
+}
+
+#[test]
+fn continues_array_elements_on_the_dash_line() {
+    let value = json!([{"id": 1, "tags": ["a"]}, [1, [2, 3]], [], {}]);
+    assert_eq!(value.to_yaml_like(), "- id: 1\n  tags:\n    - a\n- - 1\n  - - 2\n    - 3\n- []\n- {}");
+}
+
+#[test]
+fn quotes_strings_that_would_read_differently() {
+    let value = json!({"plain": "hello world", "true": "yes", "num": "42", "a: b": "", "x": "line\nbreak", "pad": " pad", "y": 1});
+    assert_eq!(
+        value.to_yaml_like(),
+        "plain: hello world\n\"true\": \"yes\"\nnum: \"42\"\n\"a: b\": \"\"\nx: \"line\\nbreak\"\npad: \" pad\"\n\"y\": 1"
+    );
+    assert_eq!(json!("~").to_yaml_like(), "\"~\"");
+    assert_eq!(json!(1.5).to_yaml_like(), "1.5");
+    assert_eq!(json!([]).to_yaml_like(), "[]");
+}